@@ -0,0 +1,152 @@
+//! Turntable rendering: sweeps the camera once around a walk and writes out
+//! either a numbered frame sequence (PNG with `--features png`, PPM
+//! otherwise) or a single animated GIF.
+
+use crate::camera::{Orbit, ProjectionMode};
+use crate::space::Pixel3D;
+use eframe::egui;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How a [`render_turntable`] sweep is shaped.
+pub struct TurntableParams {
+    pub frames: usize,
+    pub camera_radius: f64,
+    pub focal_length: f64,
+    /// Oscillate the radius through one sine cycle over the sweep, in addition
+    /// to the azimuth rotation, for a slight dolly in/out as the camera turns.
+    pub oscillate_radius: bool,
+    pub width: usize,
+    pub height: usize,
+    pub projection_mode: ProjectionMode,
+}
+
+/// Builds the orbiting camera for `frame` of `frames`, sweeping azimuth 0..360°
+/// around a stationary `Orbit::center`.
+fn orbit_for_frame(frame: usize, params: &TurntableParams) -> Orbit {
+    let azimuth_degrees = 360.0 * frame as f64 / params.frames as f64;
+
+    let radius = if params.oscillate_radius {
+        params.camera_radius * (1.0 + 0.2 * azimuth_degrees.to_radians().sin())
+    } else {
+        params.camera_radius
+    };
+
+    let mut orbit = Orbit::from_euler_degrees(
+        radius,
+        params.focal_length,
+        params.width,
+        params.height,
+        azimuth_degrees,
+        0.0,
+        0.0,
+    );
+    orbit.set_projection_mode(params.projection_mode);
+
+    orbit
+}
+
+/// Renders one frame per step of a full camera rotation around `pixels`,
+/// reusing [`Orbit`]'s projection, so the near/far clip applies as it does in
+/// the interactive viewer. Each frame's orbit starts from [`Orbit`]'s defaults,
+/// so depth of field and fog stay off.
+pub fn render_turntable(pixels: &[Pixel3D], params: &TurntableParams) -> Vec<egui::ColorImage> {
+    (0..params.frames)
+        .map(|frame| {
+            orbit_for_frame(frame, params)
+                .projection()
+                .map_to_pixels2d(pixels)
+        })
+        .collect()
+}
+
+/// Writes each frame as a numbered PPM file, e.g. `frame_0000.ppm`, into `output_dir`.
+pub fn write_frame_sequence(frames: &[egui::ColorImage], output_dir: &str) -> io::Result<()> {
+    fs::create_dir_all(output_dir)?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let path = Path::new(output_dir).join(format!("frame_{:04}.ppm", i));
+        fs::write(path, to_ppm(frame))?;
+    }
+
+    Ok(())
+}
+
+/// Writes a [`egui::ColorImage`] as an ASCII (P3) PPM image.
+fn to_ppm(frame: &egui::ColorImage) -> String {
+    let [width, height] = frame.size;
+    let mut ppm = format!("P3\n{} {}\n255\n", width, height);
+
+    for row in frame.pixels.chunks(width) {
+        let line = row
+            .iter()
+            .map(|pixel| format!("{} {} {}", pixel.r(), pixel.g(), pixel.b()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        ppm.push_str(&line);
+        ppm.push('\n');
+    }
+
+    ppm
+}
+
+/// Writes each frame as a numbered PNG file, e.g. `frame_0000.png`, into
+/// `output_dir`.
+///
+/// Requires the `png` feature, which pulls in the `image` crate; without it,
+/// a non-`.gif` `--output` falls back to a PPM frame sequence instead (see
+/// `main`).
+#[cfg(feature = "png")]
+pub fn write_frame_sequence_png(frames: &[egui::ColorImage], output_dir: &str) -> io::Result<()> {
+    fs::create_dir_all(output_dir)?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let [width, height] = frame.size;
+        let rgba: Vec<u8> = frame
+            .pixels
+            .iter()
+            .flat_map(|pixel| [pixel.r(), pixel.g(), pixel.b(), pixel.a()])
+            .collect();
+
+        let path = Path::new(output_dir).join(format!("frame_{:04}.png", i));
+        image::save_buffer(path, &rgba, width as u32, height as u32, image::ColorType::Rgba8)
+            .map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+/// Encodes frames into a single animated GIF at `path`, played back at `fps`.
+///
+/// Requires the `gif` feature, which pulls in the `gif` crate; without it,
+/// `--output *.gif` falls back to a PPM frame sequence instead (see `main`).
+#[cfg(feature = "gif")]
+pub fn write_gif(frames: &[egui::ColorImage], path: &str, fps: u32) -> io::Result<()> {
+    use gif::{Encoder, Frame, Repeat};
+
+    let Some([width, height]) = frames.first().map(|frame| frame.size) else {
+        return Ok(());
+    };
+
+    let delay_centiseconds = (100 / fps.max(1)) as u16;
+    let mut file = fs::File::create(path)?;
+    let mut encoder = Encoder::new(&mut file, width as u16, height as u16, &[])
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for frame in frames {
+        let mut rgba: Vec<u8> = frame
+            .pixels
+            .iter()
+            .flat_map(|pixel| [pixel.r(), pixel.g(), pixel.b(), pixel.a()])
+            .collect();
+        let mut gif_frame = Frame::from_rgba_speed(width as u16, height as u16, &mut rgba, 10);
+        gif_frame.delay = delay_centiseconds;
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,25 @@
 use eframe::egui;
 
-use crate::{camera::Orbit, space::Pixel3D, SIZE};
+use crate::{
+    camera::{Orbit, ProjectionMode},
+    space::{Pixel3D, Tuple3D},
+    SIZE,
+};
+
+/// How dragging and clicking the central image affects the scene.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InteractionMode {
+    /// Dragging rotates the camera around the walk.
+    Orbit,
+    /// Clicking casts a ray and highlights the nearest prime point.
+    Select,
+}
+
+/// Degrees of camera rotation per pixel of mouse drag.
+const DRAG_SENSITIVITY: f32 = 0.3;
+/// Maximum perpendicular distance (in world units) between a point and the
+/// click ray for the click to select that point.
+const PICK_THRESHOLD: f64 = 6.0;
 
 pub struct PrimeWalkApp {
     pixels: Vec<Pixel3D>,
@@ -8,22 +27,40 @@ pub struct PrimeWalkApp {
     texture: Option<egui::TextureHandle>,
     default_camera_radius: f64,
     default_focal_length: f64,
+    interaction_mode: InteractionMode,
+    selected: Option<(usize, Tuple3D)>,
+    points_tested: usize,
+    points_drawn: usize,
 }
 
 impl PrimeWalkApp {
-    fn new(pixels: Vec<Pixel3D>, default_camera_radius: f64, default_focal_length: f64) -> Self {
+    fn new(
+        pixels: Vec<Pixel3D>,
+        default_camera_radius: f64,
+        default_focal_length: f64,
+        projection_mode: ProjectionMode,
+    ) -> Self {
+        let mut orbit = Orbit::new(default_camera_radius, default_focal_length, SIZE, SIZE);
+        orbit.set_projection_mode(projection_mode);
+
         Self {
             pixels,
-            orbit: Orbit::new(default_camera_radius, default_focal_length, SIZE, SIZE),
+            orbit,
             texture: None,
             default_camera_radius,
             default_focal_length,
+            interaction_mode: InteractionMode::Orbit,
+            selected: None,
+            points_tested: 0,
+            points_drawn: 0,
         }
     }
 
     fn update_image(&mut self, ctx: &egui::Context) {
         let mut projection = self.orbit.projection();
         let color_image = projection.map_to_pixels2d(&self.pixels);
+        self.points_tested = projection.points_tested();
+        self.points_drawn = projection.points_drawn();
 
         if let Some(texture) = &mut self.texture {
             texture.set(color_image, egui::TextureOptions::default());
@@ -95,6 +132,22 @@ impl eframe::App for PrimeWalkApp {
                 self.orbit.set_center(new_center);
                 needs_update = true;
             }
+            if i.key_down(egui::Key::N) {
+                if i.modifiers.shift {
+                    self.orbit.inc_near();
+                } else {
+                    self.orbit.dec_near();
+                }
+                needs_update = true;
+            }
+            if i.key_down(egui::Key::V) {
+                if i.modifiers.shift {
+                    self.orbit.inc_far();
+                } else {
+                    self.orbit.dec_far();
+                }
+                needs_update = true;
+            }
             if i.key_down(egui::Key::D) {
                 self.orbit
                     .reset_to_defaults(self.default_camera_radius, self.default_focal_length);
@@ -108,8 +161,83 @@ impl eframe::App for PrimeWalkApp {
                 }
                 needs_update = true;
             }
+            if i.key_down(egui::Key::O) {
+                if i.modifiers.shift {
+                    self.orbit.inc_aperture();
+                } else {
+                    self.orbit.dec_aperture();
+                }
+                needs_update = true;
+            }
+            if i.key_down(egui::Key::P) {
+                if i.modifiers.shift {
+                    self.orbit.inc_focus_distance();
+                } else {
+                    self.orbit.dec_focus_distance();
+                }
+                needs_update = true;
+            }
+            if i.key_down(egui::Key::U) {
+                if i.modifiers.shift {
+                    self.orbit.inc_focal_x();
+                } else {
+                    self.orbit.dec_focal_x();
+                }
+                needs_update = true;
+            }
+            if i.key_down(egui::Key::I) {
+                if i.modifiers.shift {
+                    self.orbit.inc_focal_y();
+                } else {
+                    self.orbit.dec_focal_y();
+                }
+                needs_update = true;
+            }
+            if i.key_down(egui::Key::G) {
+                if i.modifiers.shift {
+                    self.orbit.inc_fog_start();
+                } else {
+                    self.orbit.dec_fog_start();
+                }
+                needs_update = true;
+            }
+            if i.key_down(egui::Key::T) {
+                if i.modifiers.shift {
+                    self.orbit.inc_fog_end();
+                } else {
+                    self.orbit.dec_fog_end();
+                }
+                needs_update = true;
+            }
         });
 
+        if ctx.input(|i| i.key_pressed(egui::Key::M)) {
+            self.interaction_mode = match self.interaction_mode {
+                InteractionMode::Orbit => InteractionMode::Select,
+                InteractionMode::Select => InteractionMode::Orbit,
+            };
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::C)) {
+            self.orbit.frame_all(&self.pixels);
+            needs_update = true;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Y)) {
+            self.orbit.toggle_fog();
+            needs_update = true;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::X)) {
+            self.orbit.cycle_fog_color();
+            needs_update = true;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::B)) {
+            self.orbit.cycle_projection_mode();
+            needs_update = true;
+        }
+
         if needs_update || self.texture.is_none() {
             self.update_image(ctx);
         }
@@ -117,9 +245,11 @@ impl eframe::App for PrimeWalkApp {
         egui::SidePanel::left("info_panel").show(ctx, |ui| {
             ui.heading("Camera Info");
             ui.separator();
-            ui.label(format!("Azimuth: {}", self.orbit.azimuth()));
-            ui.label(format!("Polar: {}", self.orbit.polar()));
-            ui.label(format!("Rotation: {}", self.orbit.rotation()));
+            let forward = self.orbit.get_normal_vector().scale(-1.0);
+            ui.label(format!(
+                "Forward: ({:.2}, {:.2}, {:.2})",
+                forward.x, forward.y, forward.z
+            ));
             let center = self.orbit.center();
             ui.label(format!(
                 "Center: ({:.1}, {:.1}, {:.1})",
@@ -127,6 +257,39 @@ impl eframe::App for PrimeWalkApp {
             ));
             ui.label(format!("Camera radius: {:.1}", self.orbit.camera_radius()));
             ui.label(format!("Focal length: {:.1}", self.orbit.focal_length()));
+            ui.label(format!("Aperture: {:.1}", self.orbit.aperture()));
+            ui.label(format!("Focus distance: {:.1}", self.orbit.focus_distance()));
+            ui.label(format!("Near: {:.1}", self.orbit.near()));
+            ui.label(format!("Far: {:.1}", self.orbit.far()));
+            let (fr, fg, fb) = self.orbit.fog_color();
+            ui.label(format!(
+                "Fog: {} ({:.1} - {:.1}, color {},{},{})",
+                if self.orbit.fog_enabled() { "on" } else { "off" },
+                self.orbit.fog_start(),
+                self.orbit.fog_end(),
+                fr,
+                fg,
+                fb
+            ));
+            ui.label(format!(
+                "Points: {}/{} tested ({:.0}% culled)",
+                self.points_drawn,
+                self.points_tested,
+                if self.points_tested == 0 {
+                    0.0
+                } else {
+                    100.0 * (1.0 - self.points_drawn as f64 / self.points_tested as f64)
+                }
+            ));
+            ui.label(format!("Mode: {:?}", self.interaction_mode));
+            ui.label(format!("Projection: {:?}", self.orbit.projection_mode()));
+            match &self.selected {
+                Some((index, coordinate)) => ui.label(format!(
+                    "Selected: #{} ({:.1}, {:.1}, {:.1})",
+                    index, coordinate.x, coordinate.y, coordinate.z
+                )),
+                None => ui.label("Selected: none"),
+            };
 
             ui.add_space(10.0);
             ui.heading("Keyboard Controls");
@@ -141,6 +304,16 @@ impl eframe::App for PrimeWalkApp {
             ui.label("Camera:");
             ui.label("  Z/Shift+Z - Distance");
             ui.label("  F/Shift+F - Focal Length");
+            ui.label("  O/Shift+O - Aperture");
+            ui.label("  P/Shift+P - Focus Distance");
+            ui.label("  U/Shift+U - Horizontal focal scale");
+            ui.label("  I/Shift+I - Vertical focal scale");
+            ui.label("  N/Shift+N - Near clip distance (lower below 0 to see behind the camera in Equirectangular/Fisheye)");
+            ui.label("  V/Shift+V - Far clip distance");
+            ui.label("  G/Shift+G - Fog start distance");
+            ui.label("  T/Shift+T - Fog end distance");
+            ui.label("  Y - Toggle fog");
+            ui.label("  X - Cycle fog color");
 
             ui.add_space(5.0);
             ui.label("Center Position:");
@@ -150,15 +323,55 @@ impl eframe::App for PrimeWalkApp {
 
             ui.add_space(5.0);
             ui.label("D - Reset to defaults");
+            ui.label("C - Frame all points");
+            ui.label("M - Toggle orbit-drag / click-to-select mode");
+            ui.label("B - Cycle projection mode (pinhole/equirectangular/fisheye)");
+            ui.label("Drag image - Orbit camera (orbit mode)");
+            ui.label("Click image - Select nearest point (select mode)");
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            let available = ui.available_size();
+            let (width, height) = (available.x.round() as usize, available.y.round() as usize);
+
+            if width > 0
+                && height > 0
+                && (width, height) != (self.orbit.screen_width(), self.orbit.screen_height())
+            {
+                self.orbit.set_screen_size(width, height);
+                self.update_image(ctx);
+            }
+
             if let Some(texture) = &self.texture {
-                egui::Frame::new()
+                let response = egui::Frame::new()
                     .stroke(egui::Stroke::new(2.0, egui::Color32::GREEN))
-                    .show(ui, |ui| {
-                        ui.image(texture);
-                    });
+                    .show(ui, |ui| ui.image(texture))
+                    .response;
+                let response = response.interact(egui::Sense::click_and_drag());
+
+                match self.interaction_mode {
+                    InteractionMode::Orbit => {
+                        if response.dragged() {
+                            let delta = response.drag_delta();
+                            self.orbit.drag(delta.x, -delta.y, DRAG_SENSITIVITY);
+                            self.update_image(ctx);
+                        }
+                    }
+                    InteractionMode::Select => {
+                        if response.clicked() {
+                            if let Some(pos) = response.interact_pointer_pos() {
+                                let rect = response.rect;
+                                let px = (pos.x - rect.min.x) as usize;
+                                let py = (pos.y - rect.min.y) as usize;
+
+                                let projection = self.orbit.projection();
+                                self.selected = projection
+                                    .pick(&self.pixels, px, py, PICK_THRESHOLD)
+                                    .map(|index| (index, self.pixels[index].coordinate));
+                            }
+                        }
+                    }
+                }
             }
         });
 
@@ -168,7 +381,12 @@ impl eframe::App for PrimeWalkApp {
     }
 }
 
-pub fn image(pixels: Vec<Pixel3D>, default_camera_radius: f64, default_focal_length: f64) {
+pub fn image(
+    pixels: Vec<Pixel3D>,
+    default_camera_radius: f64,
+    default_focal_length: f64,
+    projection_mode: ProjectionMode,
+) {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([SIZE as f32, SIZE as f32])
@@ -179,6 +397,13 @@ pub fn image(pixels: Vec<Pixel3D>, default_camera_radius: f64, default_focal_len
     let _ = eframe::run_native(
         "3D Prime Walk",
         options,
-        Box::new(|_cc| Ok(Box::new(PrimeWalkApp::new(pixels, default_camera_radius, default_focal_length)))),
+        Box::new(|_cc| {
+            Ok(Box::new(PrimeWalkApp::new(
+                pixels,
+                default_camera_radius,
+                default_focal_length,
+                projection_mode,
+            )))
+        }),
     );
 }
@@ -1,9 +1,7 @@
-use crate::space::{Pixel3D, Plane, Tuple3D};
+use crate::space::{Pixel3D, Plane, Quaternion, Tuple3D};
 use eframe::egui;
 use std::f64::consts::PI;
 
-use crate::SIZE;
-
 #[derive(Debug)]
 pub struct Screen {
     screen_center: Tuple3D,
@@ -15,6 +13,7 @@ pub struct Screen {
     height: usize,
     pub corners: [Tuple3D; 4],
     normal: Tuple3D,
+    mode: ProjectionMode,
 }
 
 impl Screen {
@@ -24,6 +23,7 @@ impl Screen {
         vector_v: Tuple3D,
         width: usize,
         height: usize,
+        mode: ProjectionMode,
     ) -> Self {
         let half_width = width as f64 / 2.0;
         let half_height = height as f64 / 2.0;
@@ -58,10 +58,42 @@ impl Screen {
             height,
             corners: [top_left, top_right, bottom_left, bottom_right],
             normal,
+            mode,
         }
     }
 
+    pub fn mode(&self) -> ProjectionMode {
+        self.mode
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Inverts the pinhole pixel mapping, returning the world point that a pixel
+    /// on the screen plane corresponds to.
+    pub fn unproject(&self, px: usize, py: usize) -> Tuple3D {
+        let half_width = self.width as f64 / 2.0;
+        let half_height = self.height as f64 / 2.0;
+
+        self.screen_center
+            .add(&self.vector_u.scale(px as f64 - half_width))
+            .add(&self.vector_v.scale(py as f64 - half_height))
+    }
+
     pub fn project(&self, camera: &Tuple3D, target: &Tuple3D) -> Option<(usize, usize)> {
+        match self.mode {
+            ProjectionMode::Pinhole => self.project_pinhole(camera, target),
+            ProjectionMode::Equirectangular => self.project_equirectangular(camera, target),
+            ProjectionMode::Fisheye => self.project_fisheye(camera, target),
+        }
+    }
+
+    fn project_pinhole(&self, camera: &Tuple3D, target: &Tuple3D) -> Option<(usize, usize)> {
         let dist1 = camera.sub(&self.screen_center).dot(&self.normal);
         let dist2 = target.sub(&self.screen_center).dot(&self.normal);
         if dist1 * dist2 <= 0.0 || dist1.abs() >= dist2.abs() {
@@ -95,41 +127,211 @@ impl Screen {
 
         Some((pixel_x as usize, pixel_y as usize))
     }
+
+    /// Maps a direction onto a full 360-degree panorama. Unlike the pinhole mode,
+    /// points behind the camera still project onto the back half of the image.
+    fn project_equirectangular(&self, camera: &Tuple3D, target: &Tuple3D) -> Option<(usize, usize)> {
+        let d = normalize(&target.sub(camera));
+
+        let longitude = d.dot(&self.vector_u).atan2(d.dot(&self.normal));
+        let latitude = d.dot(&self.vector_v).clamp(-1.0, 1.0).asin();
+
+        let pixel_x = ((longitude / (2.0 * PI) + 0.5) * self.width as f64).round();
+        let pixel_y = ((0.5 - latitude / PI) * self.height as f64).round();
+
+        if pixel_x < 0.0
+            || pixel_x >= self.width as f64
+            || pixel_y < 0.0
+            || pixel_y >= self.height as f64
+        {
+            return None;
+        }
+
+        Some((pixel_x as usize, pixel_y as usize))
+    }
+
+    /// Maps a direction onto a circular fisheye disc: the angle away from the
+    /// screen normal becomes distance from the image center.
+    fn project_fisheye(&self, camera: &Tuple3D, target: &Tuple3D) -> Option<(usize, usize)> {
+        let d = normalize(&target.sub(camera));
+
+        let theta = d.dot(&self.normal).clamp(-1.0, 1.0).acos();
+        let phi = d.dot(&self.vector_v).atan2(d.dot(&self.vector_u));
+
+        let max_radius = self.width.min(self.height) as f64 / 2.0;
+        let r = (theta / FISHEYE_THETA_MAX) * max_radius;
+
+        let center_x = self.width as f64 / 2.0;
+        let center_y = self.height as f64 / 2.0;
+
+        let pixel_x = (center_x + r * phi.cos()).round();
+        let pixel_y = (center_y + r * phi.sin()).round();
+
+        if pixel_x < 0.0
+            || pixel_x >= self.width as f64
+            || pixel_y < 0.0
+            || pixel_y >= self.height as f64
+        {
+            return None;
+        }
+
+        Some((pixel_x as usize, pixel_y as usize))
+    }
+}
+
+fn normalize(v: &Tuple3D) -> Tuple3D {
+    let length = v.dot(v).sqrt();
+    v.scale(1.0 / length)
 }
 
-const FULL_CIRCLE: u16 = 360;
-const HALF_CIRCLE: u16 = 180;
+fn blend(base: egui::Color32, over: egui::Color32, alpha: f64) -> egui::Color32 {
+    let lerp = |b: u8, o: u8| (b as f64 + (o as f64 - b as f64) * alpha).round() as u8;
+
+    egui::Color32::from_rgb(
+        lerp(base.r(), over.r()),
+        lerp(base.g(), over.g()),
+        lerp(base.b(), over.b()),
+    )
+}
 
-fn rad(angle: u16) -> f64 {
-    (angle as f64 * PI) / HALF_CIRCLE as f64
+/// How a 3D point maps onto the 2D image.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ProjectionMode {
+    /// Flat projection onto the screen plane, as seen through a pinhole camera.
+    Pinhole,
+    /// 360-degree panorama: longitude maps to x, latitude maps to y. Unlike
+    /// Pinhole this can show the back hemisphere, but the near/far clip still
+    /// applies in every mode and a point behind the camera has negative
+    /// depth - with the default (positive) `near` it's culled like anything
+    /// else closer than the near plane. Lower `near` below 0 (N/Shift+N in
+    /// the viewer) to bring the back hemisphere into view.
+    Equirectangular,
+    /// Circular fisheye: angle from the screen normal (which points from the
+    /// camera away from the scene) maps to distance from center.
+    /// Shares Equirectangular's need to lower `near` below 0 to see behind the camera.
+    Fisheye,
 }
 
+/// The full angular range mapped across the fisheye disc.
+const FISHEYE_THETA_MAX: f64 = PI;
+
 pub struct Projection {
     camera: Tuple3D,
     screen: Screen,
-    planes: [Plane; 4],
+    planes: [Plane; 6],
     pixel_buffer: Vec<egui::Color32>,
     distance_buffer: Vec<f64>,
+    focal_length: f64,
+    aperture: f64,
+    focus_distance: f64,
+    near: f64,
+    far: f64,
+    fog_enabled: bool,
+    fog_start: f64,
+    fog_end: f64,
+    fog_color: egui::Color32,
+    points_tested: usize,
+    points_drawn: usize,
 }
 
 impl Projection {
-    fn new(camera: Tuple3D, screen: Screen) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        camera: Tuple3D,
+        screen: Screen,
+        focal_length: f64,
+        aperture: f64,
+        focus_distance: f64,
+        near: f64,
+        far: f64,
+        fog_enabled: bool,
+        fog_start: f64,
+        fog_end: f64,
+        fog_color: (u8, u8, u8),
+    ) -> Self {
         let [top_left, top_right, bottom_left, bottom_right] = screen.corners;
 
+        // The screen normal points from the camera towards the screen plane,
+        // i.e. away from the scene (see `project_pinhole`'s sign convention),
+        // so the view direction into the scene is its negation; near/far
+        // planes sit `near`/`far` units out along that view direction.
+        let near_point = camera.sub(&screen.normal.scale(near));
+        let far_point = camera.sub(&screen.normal.scale(far));
+
         let planes = [
             Plane::new(top_left, top_right, camera),
             Plane::new(top_right, bottom_right, camera),
             Plane::new(bottom_left, bottom_right, camera),
             Plane::new(bottom_left, top_left, camera),
+            Plane::new(
+                near_point,
+                near_point.add(&screen.vector_u),
+                near_point.add(&screen.vector_v),
+            ),
+            Plane::new(
+                far_point,
+                far_point.add(&screen.vector_u),
+                far_point.add(&screen.vector_v),
+            ),
         ];
 
+        let buffer_len = screen.width() * screen.height();
+
         Self {
             camera,
             screen,
             planes,
-            pixel_buffer: vec![egui::Color32::BLACK; SIZE * SIZE],
-            distance_buffer: vec![f64::MAX; SIZE * SIZE],
+            pixel_buffer: vec![egui::Color32::BLACK; buffer_len],
+            distance_buffer: vec![f64::MAX; buffer_len],
+            focal_length,
+            aperture,
+            focus_distance,
+            near,
+            far,
+            fog_enabled,
+            fog_start,
+            fog_end,
+            fog_color: egui::Color32::from_rgb(fog_color.0, fog_color.1, fog_color.2),
+            points_tested: 0,
+            points_drawn: 0,
+        }
+    }
+
+    /// Camera-space depth of `coord` along the view direction (camera towards
+    /// the scene, the negation of the screen normal), used to cull points
+    /// outside the `[near, far]` clip range before projecting them.
+    fn depth(&self, coord: &Tuple3D) -> f64 {
+        self.camera.sub(coord).dot(&self.screen.normal)
+    }
+
+    /// Whether `depth` survives the near/far clip, applied the same way in
+    /// every projection mode. In Equirectangular/Fisheye mode this also culls
+    /// the back hemisphere at the default (positive) `near`, since a point
+    /// behind the camera has negative depth - see [`ProjectionMode::Equirectangular`].
+    fn within_clip_range(&self, depth: f64) -> bool {
+        depth >= self.near && depth <= self.far
+    }
+
+    /// Number of walk points tested against the clip range in the last
+    /// [`map_to_pixels2d`] call.
+    pub fn points_tested(&self) -> usize {
+        self.points_tested
+    }
+
+    /// Number of walk points that survived clipping and were actually
+    /// projected onto the screen in the last [`map_to_pixels2d`] call.
+    pub fn points_drawn(&self) -> usize {
+        self.points_drawn
+    }
+
+    /// Radius (in pixels) of the circle of confusion for a point at `dist` from
+    /// the camera, given the lens's aperture and focus distance.
+    fn circle_of_confusion(&self, dist: f64) -> f64 {
+        if self.aperture <= 0.0 || dist <= 0.0 {
+            return 0.0;
         }
+
+        self.aperture * ((dist - self.focus_distance).abs() / dist) * (self.focal_length / self.focus_distance)
     }
 
     fn draw_line(
@@ -138,6 +340,7 @@ impl Projection {
         to: (usize, usize),
         color: egui::Color32,
         distance: f64,
+        coc: f64,
         pixels2d: &mut [egui::Color32],
         distances: &mut [f64],
     ) {
@@ -152,10 +355,15 @@ impl Projection {
 
         let mut x = x0;
         let mut y = y0;
+        let (width, height) = (self.screen.width(), self.screen.height());
 
         loop {
-            if x >= 0 && x < SIZE as isize && y >= 0 && y < SIZE as isize {
-                let index = (y as usize) * SIZE + (x as usize);
+            // The fast path (coc <= 1 pixel) matches the original sharp,
+            // single-z-tested write exactly.
+            if coc > 1.0 {
+                self.splat(x, y, color, distance, coc, pixels2d, distances);
+            } else if x >= 0 && x < width as isize && y >= 0 && y < height as isize {
+                let index = (y as usize) * width + (x as usize);
                 if distance < distances[index] {
                     pixels2d[index] = color;
                     distances[index] = distance;
@@ -178,24 +386,104 @@ impl Projection {
         }
     }
 
+    /// Splats `color` into a disc of radius `coc` pixels around `(cx, cy)`,
+    /// attenuating alpha by `1/coc^2` and still respecting the distance buffer so
+    /// nearer, crisp geometry wins over a blurred, farther point.
+    #[allow(clippy::too_many_arguments)]
+    fn splat(
+        &self,
+        cx: isize,
+        cy: isize,
+        color: egui::Color32,
+        distance: f64,
+        coc: f64,
+        pixels2d: &mut [egui::Color32],
+        distances: &mut [f64],
+    ) {
+        let radius = coc.ceil() as isize;
+        let alpha = (1.0 / (coc * coc)).clamp(0.0, 1.0);
+        let (width, height) = (self.screen.width(), self.screen.height());
+
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if (dx * dx + dy * dy) as f64 > coc * coc {
+                    continue;
+                }
+
+                let x = cx + dx;
+                let y = cy + dy;
+                if x < 0 || x >= width as isize || y < 0 || y >= height as isize {
+                    continue;
+                }
+
+                let index = (y as usize) * width + (x as usize);
+                if distance < distances[index] {
+                    pixels2d[index] = blend(pixels2d[index], color, alpha);
+                    distances[index] = distance;
+                }
+            }
+        }
+    }
+
+    /// Blends every written pixel towards `fog_color` by `(dist - fog_start) /
+    /// (fog_end - fog_start)`, clamped to `[0, 1]`, using the stored squared
+    /// distance so far segments fade out while near ones stay vivid. A no-op
+    /// when `fog_start == fog_end`.
+    fn apply_fog(&self, pixels2d: &mut [egui::Color32], distances: &[f64]) {
+        let span = self.fog_end - self.fog_start;
+        if span.abs() < 1e-10 {
+            return;
+        }
+
+        for (color, &squared_distance) in pixels2d.iter_mut().zip(distances) {
+            if squared_distance >= f64::MAX {
+                continue;
+            }
+
+            let dist = squared_distance.sqrt();
+            let alpha = ((dist - self.fog_start) / span).clamp(0.0, 1.0);
+            *color = blend(*color, self.fog_color, alpha);
+        }
+    }
+
     fn pixel_color(pixel3d: &Pixel3D) -> egui::Color32 {
         egui::Color32::from_rgb(pixel3d.color.0, pixel3d.color.1, pixel3d.color.2)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn draw_line_from_prev(
         &self,
         prev_coord: Option<(f64, (usize, usize))>,
         current_pos: (usize, usize),
         color: egui::Color32,
         distance: f64,
+        coc: f64,
         pixels2d: &mut [egui::Color32],
         distances: &mut [f64],
     ) {
         if let Some((_, prev_xy)) = prev_coord {
-            self.draw_line(prev_xy, current_pos, color, distance, pixels2d, distances);
+            if self.is_longitude_wraparound(prev_xy, current_pos) {
+                // The segment would otherwise streak across the whole panorama at
+                // the +-180 degree seam; split it by simply not connecting the two
+                // halves, same as when a point falls off either edge of the image.
+                return;
+            }
+
+            self.draw_line(prev_xy, current_pos, color, distance, coc, pixels2d, distances);
         }
     }
 
+    /// Detects a horizontal jump consistent with wrapping around the +-180 degree
+    /// seam of an equirectangular panorama.
+    fn is_longitude_wraparound(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if self.screen.mode() != ProjectionMode::Equirectangular {
+            return false;
+        }
+
+        let dx = (from.0 as isize - to.0 as isize).unsigned_abs();
+        dx > self.screen.width() / 2
+    }
+
     fn handle_projected_point(
         &self,
         coord_3d: &Tuple3D,
@@ -209,12 +497,14 @@ impl Projection {
             .map(|relative_coords| {
                 let distance = self.camera.coordinate_squared_distance(coord_3d);
                 let color = Self::pixel_color(pixel3d);
+                let coc = self.circle_of_confusion(distance.sqrt());
 
                 self.draw_line_from_prev(
                     prev_coord,
                     relative_coords,
                     color,
                     distance,
+                    coc,
                     pixels2d,
                     distances,
                 );
@@ -240,27 +530,40 @@ impl Projection {
     }
 
     pub fn map_to_pixels2d(&mut self, pixels3d: &[Pixel3D]) -> egui::ColorImage {
+        let (width, height) = (self.screen.width(), self.screen.height());
+
         // Take ownership of buffers temporarily to avoid borrow conflicts
         let mut pixels2d = std::mem::take(&mut self.pixel_buffer);
         let mut distances = std::mem::take(&mut self.distance_buffer);
 
         // Ensure buffers are the right size and reset them
-        pixels2d.resize(SIZE * SIZE, egui::Color32::BLACK);
-        distances.resize(SIZE * SIZE, f64::MAX);
+        pixels2d.resize(width * height, egui::Color32::BLACK);
+        distances.resize(width * height, f64::MAX);
+
+        self.points_tested = 0;
+        self.points_drawn = 0;
 
         let mut prev_coord: Option<(f64, (usize, usize))> = None;
         let mut prev_3d_coord: Option<Tuple3D> = None;
 
         for pixel3d in pixels3d {
-            let projected = self.handle_projected_point(
-                &pixel3d.coordinate,
-                pixel3d,
-                prev_coord,
-                &mut pixels2d,
-                &mut distances,
-            );
+            self.points_tested += 1;
+
+            let depth = self.depth(&pixel3d.coordinate);
+            let projected = if self.within_clip_range(depth) {
+                self.handle_projected_point(
+                    &pixel3d.coordinate,
+                    pixel3d,
+                    prev_coord,
+                    &mut pixels2d,
+                    &mut distances,
+                )
+            } else {
+                None
+            };
 
             if projected.is_some() {
+                self.points_drawn += 1;
                 prev_coord = projected;
                 prev_3d_coord = Some(pixel3d.coordinate);
             } else {
@@ -280,35 +583,145 @@ impl Projection {
             }
         }
 
+        if self.fog_enabled {
+            self.apply_fog(&mut pixels2d, &distances);
+        }
+
         // Store buffers back for reuse (clone pixel_buffer since we return it)
         self.distance_buffer = distances;
 
         egui::ColorImage {
-            size: [SIZE, SIZE],
-            source_size: egui::Vec2::new(SIZE as f32, SIZE as f32),
+            size: [width, height],
+            source_size: egui::Vec2::new(width as f32, height as f32),
             pixels: pixels2d,
         }
     }
 
-    pub fn edge(&self, start: &Tuple3D, end: &Tuple3D) -> [Option<Tuple3D>; 4] {
+    /// Projects every walk point to screen space without rasterizing: no
+    /// z-buffer, depth-of-field splat, or fog blend, just each point's pixel
+    /// coordinate and color (or `None` for a point outside the near/far clip
+    /// range or off-screen). Index-aligned with `pixels3d`, so callers that
+    /// want to connect consecutive points - like the SVG writer - can detect
+    /// gaps the same way [`Self::map_to_pixels2d`] does.
+    pub fn project_points(&self, pixels3d: &[Pixel3D]) -> Vec<Option<((usize, usize), (u8, u8, u8))>> {
+        pixels3d
+            .iter()
+            .map(|pixel3d| {
+                let depth = self.depth(&pixel3d.coordinate);
+                if !self.within_clip_range(depth) {
+                    return None;
+                }
+
+                self.screen
+                    .project(&self.camera, &pixel3d.coordinate)
+                    .map(|xy| (xy, pixel3d.color))
+            })
+            .collect()
+    }
+
+    pub fn edge(&self, start: &Tuple3D, end: &Tuple3D) -> [Option<Tuple3D>; 6] {
         [
             self.planes[0].intersect(start, end),
             self.planes[1].intersect(start, end),
             self.planes[2].intersect(start, end),
             self.planes[3].intersect(start, end),
+            self.planes[4].intersect(start, end),
+            self.planes[5].intersect(start, end),
         ]
     }
+
+    /// Casts a ray from the camera through pixel `(px, py)` and returns the index
+    /// of the closest `Pixel3D` whose perpendicular distance to the ray is within
+    /// `threshold`, or `None` if nothing is close enough.
+    pub fn pick(&self, pixels3d: &[Pixel3D], px: usize, py: usize, threshold: f64) -> Option<usize> {
+        let q = self.screen.unproject(px, py);
+        let ray = q.sub(&self.camera);
+        let ray_len = ray.dot(&ray).sqrt();
+        if ray_len < 1e-10 {
+            return None;
+        }
+
+        pixels3d
+            .iter()
+            .enumerate()
+            .map(|(i, pixel)| {
+                let to_point = pixel.coordinate.sub(&self.camera);
+                let cross = to_point.cross(&ray);
+                let perpendicular_distance = cross.dot(&cross).sqrt() / ray_len;
+                (i, perpendicular_distance)
+            })
+            .filter(|(_, dist)| *dist <= threshold)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(i, _)| i)
+    }
 }
 
 pub struct Orbit {
-    polar: u16,
-    azimnuth: u16,
-    rotation: u16,
+    /// Orientation of the camera rig: `rotate(&(0, 0, 1))` gives the radial
+    /// vector from `center` out to the camera, `rotate(&(1, 0, 0))` and
+    /// `rotate(&(0, 1, 0))` give the screen's horizontal and vertical axes.
+    /// A unit quaternion has no poles, so nudging it never suffers the
+    /// gimbal-style coupling that the old whole-degree azimuth/polar/rotation
+    /// triple did near the top and bottom of the orbit.
+    orientation: Quaternion,
     camera_radius: f64,
     focal_length: f64,
     center: Tuple3D,
     screen_width: usize,
     screen_height: usize,
+    projection_mode: ProjectionMode,
+    aperture: f64,
+    focus_distance: f64,
+    focal_x: f64,
+    focal_y: f64,
+    near: f64,
+    far: f64,
+    fog_enabled: bool,
+    fog_start: f64,
+    fog_end: f64,
+    fog_color_index: usize,
+}
+
+/// Default near clip distance: small enough to never cull a real walk point,
+/// just close enough to discard anything sitting on top of the camera.
+const DEFAULT_NEAR: f64 = 0.1;
+/// Default far clip distance: effectively unbounded until the user dials it in.
+const DEFAULT_FAR: f64 = 1_000_000.0;
+/// Degrees applied by a single keyboard nudge (`inc_polar`, `dec_azimuth`, ...).
+const NUDGE_DEGREES: f64 = 1.0;
+/// Colors cycled through by `cycle_fog_color`: black, white, and two dusky
+/// atmospheric tints.
+const FOG_COLORS: [(u8, u8, u8); 4] = [(0, 0, 0), (255, 255, 255), (180, 200, 220), (20, 20, 30)];
+
+/// Builds the composed orientation `yaw(azimuth) * pitch(-polar) * roll(-rotation)`,
+/// reproducing the old Euler-angle camera basis exactly (see `from_euler_degrees`).
+fn euler_to_quaternion(azimuth_degrees: f64, polar_degrees: f64, rotation_degrees: f64) -> Quaternion {
+    let yaw = Quaternion::from_axis_angle(
+        Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        },
+        azimuth_degrees.to_radians(),
+    );
+    let pitch = Quaternion::from_axis_angle(
+        Tuple3D {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        },
+        -polar_degrees.to_radians(),
+    );
+    let roll = Quaternion::from_axis_angle(
+        Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        },
+        -rotation_degrees.to_radians(),
+    );
+
+    yaw * pitch * roll
 }
 
 impl Orbit {
@@ -319,9 +732,7 @@ impl Orbit {
         screen_height: usize,
     ) -> Self {
         Orbit {
-            polar: 0,
-            azimnuth: 0,
-            rotation: 0,
+            orientation: Quaternion::identity(),
             camera_radius,
             focal_length,
             center: Tuple3D {
@@ -331,220 +742,509 @@ impl Orbit {
             },
             screen_width,
             screen_height,
+            projection_mode: ProjectionMode::Pinhole,
+            aperture: 0.0,
+            focus_distance: camera_radius,
+            focal_x: 1.0,
+            focal_y: 1.0,
+            near: DEFAULT_NEAR,
+            far: DEFAULT_FAR,
+            fog_enabled: false,
+            fog_start: camera_radius,
+            fog_end: camera_radius * 3.0,
+            fog_color_index: 0,
         }
     }
 
-    pub fn projection(&self) -> Projection {
-        let a = rad(self.azimnuth);
-        let p = rad(self.polar);
-        let r = rad(self.rotation);
+    /// Convenience constructor for callers used to the old whole-degree
+    /// azimuth/polar/rotation controls; internally it's just a starting
+    /// orientation, not a stored representation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_euler_degrees(
+        camera_radius: f64,
+        focal_length: f64,
+        screen_width: usize,
+        screen_height: usize,
+        azimuth_degrees: f64,
+        polar_degrees: f64,
+        rotation_degrees: f64,
+    ) -> Self {
+        let mut orbit = Self::new(camera_radius, focal_length, screen_width, screen_height);
+        orbit.orientation = euler_to_quaternion(azimuth_degrees, polar_degrees, rotation_degrees);
+        orbit
+    }
 
-        let vec_x = a.sin() * p.cos();
-        let vec_y = p.sin();
-        let vec_z = a.cos() * p.cos();
+    /// Radial vector from `center` out to the camera.
+    fn radial_vector(&self) -> Tuple3D {
+        self.orientation.rotate(&Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        })
+    }
 
-        let camera = Tuple3D {
-            x: self.center.x + self.camera_radius * vec_x,
-            y: self.center.y + self.camera_radius * vec_y,
-            z: self.center.z + self.camera_radius * vec_z,
-        };
+    /// Screen's horizontal axis (unscaled by `focal_x`).
+    pub fn get_u_vector(&self) -> Tuple3D {
+        self.orientation.rotate(&Tuple3D {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        })
+    }
 
-        let screen_radius = self.camera_radius + self.focal_length;
-        let screen_coordinate = Tuple3D {
-            x: self.center.x + screen_radius * vec_x,
-            y: self.center.y + screen_radius * vec_y,
-            z: self.center.z + screen_radius * vec_z,
-        };
+    /// Screen's vertical axis (unscaled by `focal_y`).
+    pub fn get_v_vector(&self) -> Tuple3D {
+        self.orientation.rotate(&Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        })
+    }
 
-        let u_base = Tuple3D {
-            x: a.cos(),
-            y: 0.0,
-            z: -a.sin(),
-        };
+    /// Radial vector from `center` out to the camera; this is also the
+    /// screen's normal, i.e. the direction the camera looks back along.
+    pub fn get_normal_vector(&self) -> Tuple3D {
+        self.radial_vector()
+    }
 
-        let v_base = Tuple3D {
-            x: -a.sin() * p.sin(),
-            y: p.cos(),
-            z: -a.cos() * p.sin(),
-        };
+    pub fn screen_width(&self) -> usize {
+        self.screen_width
+    }
 
-        let cos_r = r.cos();
-        let sin_r = r.sin();
+    pub fn screen_height(&self) -> usize {
+        self.screen_height
+    }
 
-        let vector_u = Tuple3D {
-            x: cos_r * u_base.x - sin_r * v_base.x,
-            y: cos_r * u_base.y - sin_r * v_base.y,
-            z: cos_r * u_base.z - sin_r * v_base.z,
-        };
+    /// Resizes the render target, e.g. when the egui central panel is resized.
+    pub fn set_screen_size(&mut self, screen_width: usize, screen_height: usize) {
+        self.screen_width = screen_width;
+        self.screen_height = screen_height;
+    }
 
-        let vector_v = Tuple3D {
-            x: sin_r * u_base.x + cos_r * v_base.x,
-            y: sin_r * u_base.y + cos_r * v_base.y,
-            z: sin_r * u_base.z + cos_r * v_base.z,
-        };
+    pub fn focal_x(&self) -> f64 {
+        self.focal_x
+    }
 
-        Projection::new(
-            camera,
-            Screen::new(
-                screen_coordinate,
-                vector_u,
-                vector_v,
-                self.screen_width,
-                self.screen_height,
-            ),
-        )
+    pub fn focal_y(&self) -> f64 {
+        self.focal_y
     }
 
-    pub fn inc_polar(&mut self) -> Projection {
-        if self.polar == FULL_CIRCLE - 1 {
-            self.polar = 0;
-        } else {
-            self.polar += 1;
+    pub fn inc_focal_x(&mut self) -> Projection {
+        self.focal_x += 0.05;
+        self.projection()
+    }
+
+    pub fn dec_focal_x(&mut self) -> Projection {
+        if self.focal_x > 0.05 {
+            self.focal_x -= 0.05;
         }
+        self.projection()
+    }
 
+    pub fn inc_focal_y(&mut self) -> Projection {
+        self.focal_y += 0.05;
         self.projection()
     }
 
-    pub fn dec_polar(&mut self) -> Projection {
-        if self.polar == 0 {
-            self.polar = FULL_CIRCLE - 1;
-        } else {
-            self.polar -= 1;
+    pub fn dec_focal_y(&mut self) -> Projection {
+        if self.focal_y > 0.05 {
+            self.focal_y -= 0.05;
         }
+        self.projection()
+    }
+
+    pub fn aperture(&self) -> f64 {
+        self.aperture
+    }
 
+    pub fn focus_distance(&self) -> f64 {
+        self.focus_distance
+    }
+
+    pub fn inc_aperture(&mut self) -> Projection {
+        self.aperture += 0.5;
         self.projection()
     }
 
-    pub fn inc_azimuth(&mut self) -> Projection {
-        if self.azimnuth == FULL_CIRCLE - 1 {
-            self.azimnuth = 0;
-        } else {
-            self.azimnuth += 1;
+    pub fn dec_aperture(&mut self) -> Projection {
+        if self.aperture >= 0.5 {
+            self.aperture -= 0.5;
         }
+        self.projection()
+    }
 
+    pub fn inc_focus_distance(&mut self) -> Projection {
+        self.focus_distance += 5.0;
         self.projection()
     }
 
-    pub fn dec_azimuth(&mut self) -> Projection {
-        if self.azimnuth == 0 {
-            self.azimnuth = FULL_CIRCLE - 1;
-        } else {
-            self.azimnuth -= 1;
+    pub fn dec_focus_distance(&mut self) -> Projection {
+        if self.focus_distance > 5.0 {
+            self.focus_distance -= 5.0;
         }
+        self.projection()
+    }
+
+    pub fn near(&self) -> f64 {
+        self.near
+    }
 
+    pub fn far(&self) -> f64 {
+        self.far
+    }
+
+    pub fn inc_near(&mut self) -> Projection {
+        self.near += 5.0;
         self.projection()
     }
 
-    pub fn inc_rotation(&mut self) -> Projection {
-        if self.rotation == FULL_CIRCLE - 1 {
-            self.rotation = 0;
-        } else {
-            self.rotation += 1;
-        }
+    pub fn dec_near(&mut self) -> Projection {
+        self.near -= 5.0;
+        self.projection()
+    }
 
+    pub fn inc_far(&mut self) -> Projection {
+        self.far += 50.0;
         self.projection()
     }
 
-    pub fn dec_rotation(&mut self) -> Projection {
-        if self.rotation == 0 {
-            self.rotation = FULL_CIRCLE - 1;
-        } else {
-            self.rotation -= 1;
+    pub fn dec_far(&mut self) -> Projection {
+        if self.far > 50.0 {
+            self.far -= 50.0;
         }
-
         self.projection()
     }
 
-    pub fn polar(&self) -> u16 {
-        self.polar
+    pub fn fog_enabled(&self) -> bool {
+        self.fog_enabled
     }
 
-    pub fn azimuth(&self) -> u16 {
-        self.azimnuth
+    pub fn fog_start(&self) -> f64 {
+        self.fog_start
     }
 
-    pub fn rotation(&self) -> u16 {
-        self.rotation
+    pub fn fog_end(&self) -> f64 {
+        self.fog_end
     }
 
-    pub fn camera_radius(&self) -> f64 {
-        self.camera_radius
+    pub fn fog_color(&self) -> (u8, u8, u8) {
+        FOG_COLORS[self.fog_color_index]
     }
 
-    pub fn focal_length(&self) -> f64 {
-        self.focal_length
+    pub fn toggle_fog(&mut self) -> Projection {
+        self.fog_enabled = !self.fog_enabled;
+        self.projection()
     }
 
-    pub fn center(&self) -> &Tuple3D {
-        &self.center
+    pub fn cycle_fog_color(&mut self) -> Projection {
+        self.fog_color_index = (self.fog_color_index + 1) % FOG_COLORS.len();
+        self.projection()
     }
 
-    pub fn set_center(&mut self, center: Tuple3D) {
-        self.center = center;
+    pub fn inc_fog_start(&mut self) -> Projection {
+        self.fog_start += 20.0;
+        self.projection()
     }
 
-    pub fn reset_to_defaults(&mut self, default_camera_radius: f64, default_focal_length: f64) {
-        self.polar = 0;
-        self.azimnuth = 0;
-        self.rotation = 0;
-        self.camera_radius = default_camera_radius;
-        self.focal_length = default_focal_length;
-        self.center = Tuple3D {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-        };
+    pub fn dec_fog_start(&mut self) -> Projection {
+        self.fog_start = (self.fog_start - 20.0).max(0.0);
+        self.projection()
     }
 
-    pub fn inc_camera_radius(&mut self) -> Projection {
-        self.camera_radius += 1.0;
+    pub fn inc_fog_end(&mut self) -> Projection {
+        self.fog_end += 20.0;
         self.projection()
     }
 
-    pub fn dec_camera_radius(&mut self) -> Projection {
-        if self.camera_radius >= 1.0 {
-            self.camera_radius -= 1.0;
+    pub fn dec_fog_end(&mut self) -> Projection {
+        if self.fog_end > 20.0 {
+            self.fog_end -= 20.0;
         }
         self.projection()
     }
 
-    pub fn inc_focal_length(&mut self) -> Projection {
-        self.focal_length += 1.0;
-        self.projection()
+    pub fn projection_mode(&self) -> ProjectionMode {
+        self.projection_mode
     }
 
-    pub fn dec_focal_length(&mut self) -> Projection {
-        if self.focal_length > 1.0 {
-            self.focal_length -= 1.0;
-        }
+    pub fn set_projection_mode(&mut self, mode: ProjectionMode) {
+        self.projection_mode = mode;
+    }
+
+    pub fn cycle_projection_mode(&mut self) -> Projection {
+        self.projection_mode = match self.projection_mode {
+            ProjectionMode::Pinhole => ProjectionMode::Equirectangular,
+            ProjectionMode::Equirectangular => ProjectionMode::Fisheye,
+            ProjectionMode::Fisheye => ProjectionMode::Pinhole,
+        };
         self.projection()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn projection(&self) -> Projection {
+        let radial = self.radial_vector();
+        let camera = self.center.add(&radial.scale(self.camera_radius));
 
-    #[test]
-    fn test_above_camera() {
-        let s = Screen::new(
-            Tuple3D {
-                x: 0.0,
-                y: 0.0,
-                z: 42.0,
-            },
-            Tuple3D {
-                x: 1.0,
-                y: 0.0,
-                z: 0.0,
-            },
-            Tuple3D {
+        let screen_radius = self.camera_radius + self.focal_length;
+        let screen_coordinate = self.center.add(&radial.scale(screen_radius));
+
+        // vector_u/vector_v are scaled independently by focal_x/focal_y so
+        // non-square viewports and pixel aspect ratios can be corrected without
+        // stretching the image; both default to 1.0 (no distortion).
+        let vector_u = self.get_u_vector().scale(self.focal_x);
+        let vector_v = self.get_v_vector().scale(self.focal_y);
+
+        Projection::new(
+            camera,
+            Screen::new(
+                screen_coordinate,
+                vector_u,
+                vector_v,
+                self.screen_width,
+                self.screen_height,
+                self.projection_mode,
+            ),
+            self.focal_length,
+            self.aperture,
+            self.focus_distance,
+            self.near,
+            self.far,
+            self.fog_enabled,
+            self.fog_start,
+            self.fog_end,
+            self.fog_color(),
+        )
+    }
+
+    /// Rotates the orbit around the world up axis by `delta_degrees`. World-space
+    /// so the horizon stays level no matter how far the camera has pitched.
+    fn yaw(&mut self, delta_degrees: f64) {
+        let delta = Quaternion::from_axis_angle(
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            delta_degrees.to_radians(),
+        );
+        self.orientation = (delta * self.orientation).normalize();
+    }
+
+    /// Rotates the orbit around its current local right axis by `delta_degrees`.
+    /// Local-space (composed on the right) so repeated pitching never locks up
+    /// the way re-deriving a stored polar angle from scratch would near the poles.
+    fn pitch(&mut self, delta_degrees: f64) {
+        let delta = Quaternion::from_axis_angle(
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            -delta_degrees.to_radians(),
+        );
+        self.orientation = (self.orientation * delta).normalize();
+    }
+
+    /// Rotates the orbit around its current local forward axis by `delta_degrees`.
+    fn roll(&mut self, delta_degrees: f64) {
+        let delta = Quaternion::from_axis_angle(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            -delta_degrees.to_radians(),
+        );
+        self.orientation = (self.orientation * delta).normalize();
+    }
+
+    /// Updates azimuth and polar proportionally to a mouse drag delta.
+    pub fn drag(&mut self, delta_x: f32, delta_y: f32, sensitivity: f32) -> Projection {
+        self.yaw((delta_x * sensitivity) as f64);
+        self.pitch((delta_y * sensitivity) as f64);
+
+        self.projection()
+    }
+
+    pub fn inc_polar(&mut self) -> Projection {
+        self.pitch(NUDGE_DEGREES);
+        self.projection()
+    }
+
+    pub fn dec_polar(&mut self) -> Projection {
+        self.pitch(-NUDGE_DEGREES);
+        self.projection()
+    }
+
+    pub fn inc_azimuth(&mut self) -> Projection {
+        self.yaw(NUDGE_DEGREES);
+        self.projection()
+    }
+
+    pub fn dec_azimuth(&mut self) -> Projection {
+        self.yaw(-NUDGE_DEGREES);
+        self.projection()
+    }
+
+    pub fn inc_rotation(&mut self) -> Projection {
+        self.roll(NUDGE_DEGREES);
+        self.projection()
+    }
+
+    pub fn dec_rotation(&mut self) -> Projection {
+        self.roll(-NUDGE_DEGREES);
+        self.projection()
+    }
+
+    /// Points the camera at `target`, building the basis the way the classic
+    /// `look_at`/`build_camera` routine does: forward towards the target, with
+    /// `right = up x forward` and a world-up reference that switches to avoid
+    /// degenerating when forward is nearly vertical.
+    pub fn look_at(&mut self, target: Tuple3D) {
+        let camera = self.center.add(&self.get_normal_vector().scale(self.camera_radius));
+        let forward = normalize(&target.sub(&camera));
+
+        let world_up = Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+        let reference_up = if forward.y.abs() > 0.99 {
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            }
+        } else {
+            world_up
+        };
+
+        let right = normalize(&reference_up.cross(&forward));
+        let up = forward.cross(&right);
+
+        // The radial vector (local z) is the screen normal, which points back
+        // along the view direction, so the local axes are (-right, up, -forward).
+        self.orientation = Quaternion::from_basis(&right.scale(-1.0), &up, &forward.scale(-1.0));
+    }
+
+    /// Recenters on `points`' bounding sphere and pulls the camera back just far
+    /// enough, at the current focal length, for the whole walk to fit on screen.
+    pub fn frame_all(&mut self, points: &[Pixel3D]) {
+        if points.is_empty() {
+            return;
+        }
+
+        let count = points.len() as f64;
+        let sum = points.iter().fold(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            |acc, pixel| acc.add(&pixel.coordinate),
+        );
+        let centroid = sum.scale(1.0 / count);
+
+        let radius = points
+            .iter()
+            .map(|pixel| centroid.coordinate_squared_distance(&pixel.coordinate).sqrt())
+            .fold(0.0, f64::max);
+
+        self.center = centroid;
+        if radius <= 0.0 {
+            return;
+        }
+
+        let half_fov = (self.screen_width.min(self.screen_height) as f64 / 2.0 / self.focal_length).atan();
+        self.camera_radius = radius / half_fov.sin();
+    }
+
+    pub fn camera_radius(&self) -> f64 {
+        self.camera_radius
+    }
+
+    pub fn focal_length(&self) -> f64 {
+        self.focal_length
+    }
+
+    pub fn center(&self) -> &Tuple3D {
+        &self.center
+    }
+
+    pub fn set_center(&mut self, center: Tuple3D) {
+        self.center = center;
+    }
+
+    pub fn reset_to_defaults(&mut self, default_camera_radius: f64, default_focal_length: f64) {
+        self.orientation = Quaternion::identity();
+        self.camera_radius = default_camera_radius;
+        self.focal_length = default_focal_length;
+        self.center = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        self.aperture = 0.0;
+        self.focus_distance = default_camera_radius;
+        self.focal_x = 1.0;
+        self.focal_y = 1.0;
+        self.near = DEFAULT_NEAR;
+        self.far = DEFAULT_FAR;
+        self.fog_enabled = false;
+        self.fog_start = default_camera_radius;
+        self.fog_end = default_camera_radius * 3.0;
+        self.fog_color_index = 0;
+    }
+
+    pub fn inc_camera_radius(&mut self) -> Projection {
+        self.camera_radius += 1.0;
+        self.projection()
+    }
+
+    pub fn dec_camera_radius(&mut self) -> Projection {
+        if self.camera_radius >= 1.0 {
+            self.camera_radius -= 1.0;
+        }
+        self.projection()
+    }
+
+    pub fn inc_focal_length(&mut self) -> Projection {
+        self.focal_length += 1.0;
+        self.projection()
+    }
+
+    pub fn dec_focal_length(&mut self) -> Projection {
+        if self.focal_length > 1.0 {
+            self.focal_length -= 1.0;
+        }
+        self.projection()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_above_camera() {
+        let s = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 42.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
                 x: 0.0,
                 y: 1.0,
                 z: 0.0,
             },
             800,
             800,
+            ProjectionMode::Pinhole,
         );
 
         let camera = Tuple3D {
@@ -582,6 +1282,7 @@ mod tests {
             },
             100,
             100,
+            ProjectionMode::Pinhole,
         );
 
         let c1 = Tuple3D {
@@ -599,4 +1300,508 @@ mod tests {
         let a = p.project(&c1, &c2);
         assert!(a.is_none());
     }
+
+    #[test]
+    fn test_equirectangular_projects_forward_point_to_image_center() {
+        let s = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Equirectangular,
+        );
+
+        let camera = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let target = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 10.0,
+        };
+
+        let result = s.project(&camera, &target);
+        assert_eq!(result, Some((400, 400)));
+    }
+
+    #[test]
+    fn test_equirectangular_still_projects_point_behind_camera() {
+        let s = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Equirectangular,
+        );
+
+        let camera = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let target = Tuple3D {
+            x: 0.1,
+            y: 0.0,
+            z: -10.0,
+        };
+
+        assert!(s.project(&camera, &target).is_some());
+    }
+
+    #[test]
+    fn test_fisheye_projects_forward_point_to_image_center() {
+        let s = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Fisheye,
+        );
+
+        let camera = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let target = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 10.0,
+        };
+
+        let result = s.project(&camera, &target);
+        assert_eq!(result, Some((400, 400)));
+    }
+
+    #[test]
+    fn test_coc_is_zero_with_aperture_closed() {
+        let orbit = Orbit::new(600.0, 600.0, 800, 800);
+        let projection = orbit.projection();
+        assert_eq!(projection.circle_of_confusion(500.0), 0.0);
+    }
+
+    #[test]
+    fn test_coc_is_zero_at_focus_distance() {
+        let mut orbit = Orbit::new(600.0, 600.0, 800, 800);
+        orbit.inc_aperture();
+        let projection = orbit.projection();
+        assert_eq!(projection.circle_of_confusion(orbit.focus_distance()), 0.0);
+    }
+
+    #[test]
+    fn test_coc_grows_away_from_focus_distance() {
+        let mut orbit = Orbit::new(600.0, 600.0, 800, 800);
+        orbit.inc_aperture();
+        let projection = orbit.projection();
+        assert!(projection.circle_of_confusion(orbit.focus_distance() + 200.0) > 0.0);
+    }
+
+    #[test]
+    fn test_non_square_render_target_matches_screen_size() {
+        let mut orbit = Orbit::new(600.0, 600.0, 1024, 576);
+        let mut projection = orbit.projection();
+        let image = projection.map_to_pixels2d(&[]);
+        assert_eq!(image.size, [1024, 576]);
+
+        orbit.set_screen_size(640, 480);
+        let mut projection = orbit.projection();
+        let image = projection.map_to_pixels2d(&[]);
+        assert_eq!(image.size, [640, 480]);
+    }
+
+    #[test]
+    fn test_default_focal_x_focal_y_do_not_distort() {
+        let orbit = Orbit::new(600.0, 600.0, 800, 800);
+        assert_eq!(orbit.focal_x(), 1.0);
+        assert_eq!(orbit.focal_y(), 1.0);
+    }
+
+    #[test]
+    fn test_unproject_is_inverse_of_project_at_screen_center() {
+        let s = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Pinhole,
+        );
+
+        let point = s.unproject(400, 400);
+        assert_eq!(
+            point,
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_drag_rotates_radial_vector() {
+        let mut orbit = Orbit::new(600.0, 600.0, 800, 800);
+        let before = orbit.get_normal_vector();
+        orbit.drag(90.0, 0.0, 1.0);
+        let after = orbit.get_normal_vector();
+        assert!((before.x - after.x).abs() > 0.5 || (before.z - after.z).abs() > 0.5);
+    }
+
+    #[test]
+    // Relies on Tuple3D's PartialEq derive (src/space.rs) for the assert_eq!s below.
+    fn test_from_euler_degrees_matches_old_basis_at_identity() {
+        let orbit = Orbit::from_euler_degrees(600.0, 600.0, 800, 800, 0.0, 0.0, 0.0);
+        assert_eq!(orbit.get_normal_vector(), Tuple3D { x: 0.0, y: 0.0, z: 1.0 });
+        assert_eq!(orbit.get_u_vector(), Tuple3D { x: 1.0, y: 0.0, z: 0.0 });
+        assert_eq!(orbit.get_v_vector(), Tuple3D { x: 0.0, y: 1.0, z: 0.0 });
+    }
+
+    #[test]
+    fn test_look_at_points_radial_vector_away_from_target() {
+        let mut orbit = Orbit::new(600.0, 600.0, 800, 800);
+        orbit.look_at(Tuple3D {
+            x: 100_000.0,
+            y: 0.0,
+            z: 0.0,
+        });
+
+        // The radial vector points from `center` out to the camera, opposite
+        // the view direction, so looking at a target far off to +x (relative
+        // to the 600-unit camera offset) swings it towards -x.
+        let radial = orbit.get_normal_vector();
+        assert!(radial.x < -0.9);
+    }
+
+    #[test]
+    // Relies on Tuple3D's PartialEq derive (src/space.rs) for the assert_eq! below.
+    fn test_frame_all_centers_on_centroid() {
+        let mut orbit = Orbit::new(600.0, 600.0, 800, 800);
+        let points = vec![
+            Pixel3D {
+                coordinate: Tuple3D {
+                    x: 10.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                color: (255, 0, 0),
+            },
+            Pixel3D {
+                coordinate: Tuple3D {
+                    x: -10.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                color: (0, 255, 0),
+            },
+        ];
+
+        orbit.frame_all(&points);
+        assert_eq!(
+            *orbit.center(),
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+        assert!(orbit.camera_radius() > 0.0);
+    }
+
+    #[test]
+    fn test_frame_all_ignores_empty_points() {
+        let mut orbit = Orbit::new(600.0, 600.0, 800, 800);
+        let before_radius = orbit.camera_radius();
+        orbit.frame_all(&[]);
+        assert_eq!(orbit.camera_radius(), before_radius);
+    }
+
+    #[test]
+    fn test_pick_finds_point_along_center_ray() {
+        let orbit = Orbit::new(600.0, 600.0, 800, 800);
+        let projection = orbit.projection();
+
+        let pixels = vec![
+            Pixel3D {
+                coordinate: Tuple3D {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                color: (255, 0, 0),
+            },
+            Pixel3D {
+                coordinate: Tuple3D {
+                    x: 500.0,
+                    y: 500.0,
+                    z: 500.0,
+                },
+                color: (0, 255, 0),
+            },
+        ];
+
+        let picked = projection.pick(&pixels, 400, 400, 1.0);
+        assert_eq!(picked, Some(0));
+    }
+
+    #[test]
+    fn test_pick_respects_threshold() {
+        let orbit = Orbit::new(600.0, 600.0, 800, 800);
+        let projection = orbit.projection();
+
+        let pixels = vec![Pixel3D {
+            coordinate: Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            color: (255, 0, 0),
+        }];
+
+        assert_eq!(projection.pick(&pixels, 0, 0, 1.0), None);
+    }
+
+    fn equirectangular_projection_at_origin(near: f64, far: f64) -> Projection {
+        let screen = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Equirectangular,
+        );
+
+        Projection::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            screen,
+            600.0,
+            0.0,
+            600.0,
+            near,
+            far,
+            false,
+            0.0,
+            0.0,
+            (0, 0, 0),
+        )
+    }
+
+    fn pixel_behind_camera() -> Pixel3D {
+        // Depth = (camera - coord)·normal = 10, putting it 10 units along the
+        // view direction from the camera in `equirectangular_projection_at_origin`,
+        // and off-axis enough to land inside the screen bounds rather than on
+        // the longitude seam. It is "behind" only relative to the screen
+        // normal, which the panorama modes center on, so it lands on the back
+        // half of the panorama.
+        Pixel3D {
+            coordinate: Tuple3D {
+                x: 5.0,
+                y: 0.0,
+                z: -10.0,
+            },
+            color: (255, 0, 0),
+        }
+    }
+
+    #[test]
+    fn test_depth_within_clip_range_is_drawn() {
+        let mut projection = equirectangular_projection_at_origin(-100.0, 100.0);
+
+        let _ = projection.map_to_pixels2d(&[pixel_behind_camera()]);
+        assert_eq!(projection.points_tested(), 1);
+        assert_eq!(projection.points_drawn(), 1);
+    }
+
+    #[test]
+    fn test_near_plane_culls_points_closer_than_near() {
+        let mut projection = equirectangular_projection_at_origin(20.0, 100.0);
+
+        let _ = projection.map_to_pixels2d(&[pixel_behind_camera()]);
+        assert_eq!(projection.points_tested(), 1);
+        assert_eq!(projection.points_drawn(), 0);
+    }
+
+    #[test]
+    fn test_far_plane_culls_points_beyond_far() {
+        let mut projection = equirectangular_projection_at_origin(-100.0, 5.0);
+
+        let _ = projection.map_to_pixels2d(&[pixel_behind_camera()]);
+        assert_eq!(projection.points_tested(), 1);
+        assert_eq!(projection.points_drawn(), 0);
+    }
+
+    #[test]
+    fn test_fog_disabled_by_default() {
+        let orbit = Orbit::new(600.0, 600.0, 800, 800);
+        assert!(!orbit.fog_enabled());
+    }
+
+    #[test]
+    fn test_fog_disabled_leaves_colors_unchanged() {
+        let screen = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Equirectangular,
+        );
+
+        // fog_start/fog_end would fog out `pixel_behind_camera` (depth 10) if
+        // enabled, so this also exercises that `fog_enabled: false` is a no-op.
+        let mut projection = Projection::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            screen,
+            600.0,
+            0.0,
+            600.0,
+            -100.0,
+            100.0,
+            false,
+            0.0,
+            1.0,
+            (10, 20, 30),
+        );
+
+        // A lone point has no predecessor to draw a segment from, so the point
+        // is repeated to rasterize it as a zero-length segment.
+        let image = projection.map_to_pixels2d(&[pixel_behind_camera(), pixel_behind_camera()]);
+        assert!(image.pixels.contains(&egui::Color32::from_rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn test_fog_fades_point_beyond_fog_end_to_fog_color() {
+        let screen = Screen::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            800,
+            800,
+            ProjectionMode::Equirectangular,
+        );
+
+        let fog_color = (10, 20, 30);
+        let mut projection = Projection::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            screen,
+            600.0,
+            0.0,
+            600.0,
+            -100.0,
+            100.0,
+            true,
+            0.0,
+            1.0,
+            fog_color,
+        );
+
+        // `pixel_behind_camera` sits about 11.2 units from the camera, far
+        // beyond the fog_end of 1.0, so its alpha clamps to 1.0 and it's fully
+        // replaced by fog_color. It is repeated so that it is rasterized as a
+        // zero-length segment; a lone point draws nothing.
+        let image = projection.map_to_pixels2d(&[pixel_behind_camera(), pixel_behind_camera()]);
+        assert!(image
+            .pixels
+            .contains(&egui::Color32::from_rgb(fog_color.0, fog_color.1, fog_color.2)));
+    }
 }
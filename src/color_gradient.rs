@@ -1,19 +1,119 @@
+/// How two color stops are blended together.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InterpolationMode {
+    /// Linear interpolation of the raw R, G, B channels.
+    Rgb,
+    /// Interpolation in HSL space, taking the shortest arc around the hue wheel.
+    Hsl,
+    /// Interpolation in CIE L*a*b*, a perceptually uniform space: equal steps
+    /// in L*/a*/b* look like equal steps in perceived brightness/hue.
+    Lab,
+    /// Interpolation in CIE L*u*v*, another perceptually uniform space; unlike
+    /// Lab it preserves straight lines of constant hue under chromatic scaling.
+    Luv,
+}
+
+/// An easing function applied to the local segment parameter before blending.
+pub type Easing = fn(f64) -> f64;
+
+pub fn linear(t: f64) -> f64 {
+    t
+}
+
+pub fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+pub fn cubic_in(t: f64) -> f64 {
+    t * t * t
+}
+
+pub fn cubic_out(t: f64) -> f64 {
+    let u = t - 1.0;
+    u * u * u + 1.0
+}
+
 pub struct ColorGradient {
     current_step: usize,
     total_steps: usize,
-    start: (f64, f64, f64),
-    end: (f64, f64, f64),
+    stops: Vec<(f64, (u8, u8, u8))>,
+    mode: InterpolationMode,
+    easing: Easing,
 }
 
 impl ColorGradient {
+    /// Two-stop constructor kept for callers that only need a simple start/end blend.
     pub fn new(start: (u8, u8, u8), end: (u8, u8, u8), steps: usize) -> Self {
+        Self::with_mode(start, end, InterpolationMode::Rgb, steps)
+    }
+
+    /// Two-stop constructor with an explicit interpolation mode, e.g. for a
+    /// perceptually uniform `Lab`/`Luv` gradient instead of the raw-RGB default.
+    pub fn with_mode(
+        start: (u8, u8, u8),
+        end: (u8, u8, u8),
+        mode: InterpolationMode,
+        steps: usize,
+    ) -> Self {
+        Self::with_stops(vec![(0.0, start), (1.0, end)], mode, linear, steps)
+    }
+
+    /// Full constructor accepting an arbitrary, ordered list of `(position, color)` stops.
+    pub fn with_stops(
+        stops: Vec<(f64, (u8, u8, u8))>,
+        mode: InterpolationMode,
+        easing: Easing,
+        steps: usize,
+    ) -> Self {
+        assert!(stops.len() >= 2, "a gradient needs at least two stops");
+
         Self {
             current_step: 0,
             total_steps: steps,
-            start: (start.0 as f64, start.1 as f64, start.2 as f64),
-            end: (end.0 as f64, end.1 as f64, end.2 as f64),
+            stops,
+            mode,
+            easing,
         }
     }
+
+    /// Computes the blended color at `t` (`0.0..=1.0`) without mutating iterator state.
+    fn color_at_t(&self, t: f64) -> (u8, u8, u8) {
+        let segment = self
+            .stops
+            .windows(2)
+            .find(|w| t <= w[1].0)
+            .unwrap_or(&self.stops[self.stops.len() - 2..]);
+
+        let (pos0, color0) = segment[0];
+        let (pos1, color1) = segment[1];
+
+        let span = pos1 - pos0;
+        let local_t = if span.abs() < 1e-10 {
+            0.0
+        } else {
+            ((t - pos0) / span).clamp(0.0, 1.0)
+        };
+
+        let eased_t = (self.easing)(local_t);
+
+        match self.mode {
+            InterpolationMode::Rgb => lerp_rgb(color0, color1, eased_t),
+            InterpolationMode::Hsl => lerp_hsl(color0, color1, eased_t),
+            InterpolationMode::Lab => lerp_lab(color0, color1, eased_t),
+            InterpolationMode::Luv => lerp_luv(color0, color1, eased_t),
+        }
+    }
+
+    /// Computes the blended color at an arbitrary step index without mutation.
+    pub fn color_at(&self, index: usize) -> (u8, u8, u8) {
+        let t = if self.total_steps <= 1 {
+            0.0
+        } else {
+            index as f64 / (self.total_steps - 1) as f64
+        };
+
+        self.color_at_t(t)
+    }
 }
 
 impl Iterator for ColorGradient {
@@ -24,18 +124,9 @@ impl Iterator for ColorGradient {
             return None;
         }
 
-        let t = if self.total_steps == 1 {
-            0.0
-        } else {
-            self.current_step as f64 / (self.total_steps - 1) as f64
-        };
-
-        let r = (self.start.0 + (self.end.0 - self.start.0) * t).round() as u8;
-        let g = (self.start.1 + (self.end.1 - self.start.1) * t).round() as u8;
-        let b = (self.start.2 + (self.end.2 - self.start.2) * t).round() as u8;
-
+        let color = self.color_at(self.current_step);
         self.current_step += 1;
-        Some((r, g, b))
+        Some(color)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -43,3 +134,357 @@ impl Iterator for ColorGradient {
         (remaining, Some(remaining))
     }
 }
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+fn lerp_rgb(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    (
+        lerp(start.0 as f64, end.0 as f64, t).round() as u8,
+        lerp(start.1 as f64, end.1 as f64, t).round() as u8,
+        lerp(start.2 as f64, end.2 as f64, t).round() as u8,
+    )
+}
+
+fn lerp_hsl(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let (h1, s1, l1) = rgb_to_hsl(start);
+    let (h2, s2, l2) = rgb_to_hsl(end);
+
+    // Interpolate hue along the shortest arc by wrapping one endpoint by +-360.
+    let mut h2_adjusted = h2;
+    if (h2 - h1).abs() > 180.0 {
+        if h2 > h1 {
+            h2_adjusted -= 360.0;
+        } else {
+            h2_adjusted += 360.0;
+        }
+    }
+
+    let h = (lerp(h1, h2_adjusted, t) + 360.0) % 360.0;
+    let s = lerp(s1, s2, t);
+    let l = lerp(l1, l2, t);
+
+    hsl_to_rgb(h, s, l)
+}
+
+fn rgb_to_hsl(color: (u8, u8, u8)) -> (f64, f64, f64) {
+    let r = color.0 as f64 / 255.0;
+    let g = color.1 as f64 / 255.0;
+    let b = color.2 as f64 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+
+    if (max - min).abs() < 1e-10 {
+        return (0.0, 0.0, l);
+    }
+
+    let delta = max - min;
+    let s = if l > 0.5 {
+        delta / (2.0 - max - min)
+    } else {
+        delta / (max + min)
+    };
+
+    let h = if max == r {
+        ((g - b) / delta) % 6.0
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+
+    let h = (h * 60.0 + 360.0) % 360.0;
+
+    (h, s, l)
+}
+
+/// Converts HSL (`h` in degrees, `s`/`l` fractions in `[0, 1]`) to RGB via the
+/// standard chroma formula. Shared with [`crate::color_parse`] so `hsl(...)`
+/// CLI colors use the exact same conversion as HSL gradient interpolation.
+pub(crate) fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    if s.abs() < 1e-10 {
+        let gray = (l * 255.0).round() as u8;
+        return (gray, gray, gray);
+    }
+
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r1, g1, b1) = if h < 60.0 {
+        (c, x, 0.0)
+    } else if h < 120.0 {
+        (x, c, 0.0)
+    } else if h < 180.0 {
+        (0.0, c, x)
+    } else if h < 240.0 {
+        (0.0, x, c)
+    } else if h < 300.0 {
+        (x, 0.0, c)
+    } else {
+        (c, 0.0, x)
+    };
+
+    (
+        ((r1 + m) * 255.0).round() as u8,
+        ((g1 + m) * 255.0).round() as u8,
+        ((b1 + m) * 255.0).round() as u8,
+    )
+}
+
+// D65 reference white, used by both the Lab and Luv conversions below.
+const WHITE_X: f64 = 0.95047;
+const WHITE_Y: f64 = 1.0;
+const WHITE_Z: f64 = 1.08883;
+
+fn srgb_channel_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c > 0.04045 {
+        ((c + 0.055) / 1.055).powf(2.4)
+    } else {
+        c / 12.92
+    }
+}
+
+fn linear_to_srgb_channel(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let encoded = if c > 0.0031308 {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    } else {
+        c * 12.92
+    };
+    (encoded.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn rgb_to_xyz(color: (u8, u8, u8)) -> (f64, f64, f64) {
+    let r = srgb_channel_to_linear(color.0);
+    let g = srgb_channel_to_linear(color.1);
+    let b = srgb_channel_to_linear(color.2);
+
+    let x = 0.4124564 * r + 0.3575761 * g + 0.1804375 * b;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = 0.0193339 * r + 0.1191920 * g + 0.9503041 * b;
+
+    (x, y, z)
+}
+
+fn xyz_to_rgb(x: f64, y: f64, z: f64) -> (u8, u8, u8) {
+    let r = 3.2404542 * x - 1.5371385 * y - 0.4985314 * z;
+    let g = -0.9692660 * x + 1.8760108 * y + 0.0415560 * z;
+    let b = 0.0556434 * x - 0.2040259 * y + 1.0572252 * z;
+
+    (
+        linear_to_srgb_channel(r),
+        linear_to_srgb_channel(g),
+        linear_to_srgb_channel(b),
+    )
+}
+
+// The nonlinear response function shared by Lab and Luv's L* computation.
+fn lab_f(t: f64) -> f64 {
+    const DELTA: f64 = 6.0 / 29.0;
+    if t > DELTA.powi(3) {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
+
+fn lab_f_inv(t: f64) -> f64 {
+    const DELTA: f64 = 6.0 / 29.0;
+    if t > DELTA {
+        t.powi(3)
+    } else {
+        3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+    }
+}
+
+fn rgb_to_lab(color: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (x, y, z) = rgb_to_xyz(color);
+
+    let fx = lab_f(x / WHITE_X);
+    let fy = lab_f(y / WHITE_Y);
+    let fz = lab_f(z / WHITE_Z);
+
+    let l = 116.0 * fy - 16.0;
+    let a = 500.0 * (fx - fy);
+    let b = 200.0 * (fy - fz);
+
+    (l, a, b)
+}
+
+fn lab_to_rgb(l: f64, a: f64, b: f64) -> (u8, u8, u8) {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+
+    let x = WHITE_X * lab_f_inv(fx);
+    let y = WHITE_Y * lab_f_inv(fy);
+    let z = WHITE_Z * lab_f_inv(fz);
+
+    xyz_to_rgb(x, y, z)
+}
+
+fn lerp_lab(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let (l1, a1, b1) = rgb_to_lab(start);
+    let (l2, a2, b2) = rgb_to_lab(end);
+
+    lab_to_rgb(lerp(l1, l2, t), lerp(a1, a2, t), lerp(b1, b2, t))
+}
+
+// u'/v' chromaticity coordinates of the D65 white point, shared by the forward
+// and inverse Luv conversions.
+fn white_u_prime_v_prime() -> (f64, f64) {
+    let denom = WHITE_X + 15.0 * WHITE_Y + 3.0 * WHITE_Z;
+    (4.0 * WHITE_X / denom, 9.0 * WHITE_Y / denom)
+}
+
+fn rgb_to_luv(color: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (x, y, z) = rgb_to_xyz(color);
+    let (white_u, white_v) = white_u_prime_v_prime();
+
+    let denom = x + 15.0 * y + 3.0 * z;
+    let (u_prime, v_prime) = if denom.abs() < 1e-12 {
+        (white_u, white_v)
+    } else {
+        (4.0 * x / denom, 9.0 * y / denom)
+    };
+
+    let l = 116.0 * lab_f(y / WHITE_Y) - 16.0;
+    let u = 13.0 * l * (u_prime - white_u);
+    let v = 13.0 * l * (v_prime - white_v);
+
+    (l, u, v)
+}
+
+fn luv_to_rgb(l: f64, u: f64, v: f64) -> (u8, u8, u8) {
+    if l.abs() < 1e-12 {
+        return (0, 0, 0);
+    }
+
+    let (white_u, white_v) = white_u_prime_v_prime();
+    let u_prime = u / (13.0 * l) + white_u;
+    let v_prime = v / (13.0 * l) + white_v;
+
+    let y = WHITE_Y * lab_f_inv((l + 16.0) / 116.0);
+    let x = y * 9.0 * u_prime / (4.0 * v_prime);
+    let z = y * (12.0 - 3.0 * u_prime - 20.0 * v_prime) / (4.0 * v_prime);
+
+    xyz_to_rgb(x, y, z)
+}
+
+fn lerp_luv(start: (u8, u8, u8), end: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let (l1, u1, v1) = rgb_to_luv(start);
+    let (l2, u2, v2) = rgb_to_luv(end);
+
+    luv_to_rgb(lerp(l1, l2, t), lerp(u1, u2, t), lerp(v1, v2, t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_stop_rgb_gradient_matches_endpoints() {
+        let mut gradient = ColorGradient::new((255, 0, 0), (0, 0, 255), 3);
+        assert_eq!(gradient.next(), Some((255, 0, 0)));
+        assert_eq!(gradient.next(), Some((128, 0, 128)));
+        assert_eq!(gradient.next(), Some((0, 0, 255)));
+        assert_eq!(gradient.next(), None);
+    }
+
+    #[test]
+    fn test_color_at_matches_iterator() {
+        let gradient = ColorGradient::new((10, 20, 30), (200, 150, 100), 10);
+        let from_iterator = ColorGradient::new((10, 20, 30), (200, 150, 100), 10);
+        for (i, color) in from_iterator.enumerate() {
+            assert_eq!(gradient.color_at(i), color);
+        }
+    }
+
+    #[test]
+    fn test_multi_stop_gradient_hits_middle_stop() {
+        let gradient = ColorGradient::with_stops(
+            vec![(0.0, (255, 0, 0)), (0.5, (0, 255, 0)), (1.0, (0, 0, 255))],
+            InterpolationMode::Rgb,
+            linear,
+            5,
+        );
+
+        assert_eq!(gradient.color_at(2), (0, 255, 0));
+    }
+
+    #[test]
+    fn test_hsl_interpolation_takes_shortest_arc() {
+        // Red (hue 0) to magenta (hue 300) should travel through hue 330, not through green.
+        let gradient = ColorGradient::with_stops(
+            vec![(0.0, (255, 0, 0)), (1.0, (255, 0, 255))],
+            InterpolationMode::Hsl,
+            linear,
+            3,
+        );
+
+        let mid = gradient.color_at(1);
+        // Travelling the short way keeps green near zero throughout.
+        assert!(mid.1 < 10);
+    }
+
+    #[test]
+    fn test_smoothstep_easing_is_symmetric() {
+        assert!((smoothstep(0.5) - 0.5).abs() < 1e-10);
+        assert!(smoothstep(0.25) < 0.25);
+        assert!(smoothstep(0.75) > 0.75);
+    }
+
+    #[test]
+    fn test_rgb_hsl_roundtrip() {
+        for color in [(255, 0, 0), (0, 255, 0), (0, 0, 255), (128, 64, 200)] {
+            let (h, s, l) = rgb_to_hsl(color);
+            let back = hsl_to_rgb(h, s, l);
+            assert!((back.0 as i16 - color.0 as i16).abs() <= 1);
+            assert!((back.1 as i16 - color.1 as i16).abs() <= 1);
+            assert!((back.2 as i16 - color.2 as i16).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_rgb_lab_roundtrip() {
+        for color in [(255, 0, 0), (0, 255, 0), (0, 0, 255), (128, 64, 200), (0, 0, 0)] {
+            let (l, a, b) = rgb_to_lab(color);
+            let back = lab_to_rgb(l, a, b);
+            assert!((back.0 as i16 - color.0 as i16).abs() <= 1);
+            assert!((back.1 as i16 - color.1 as i16).abs() <= 1);
+            assert!((back.2 as i16 - color.2 as i16).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_rgb_luv_roundtrip() {
+        for color in [(255, 0, 0), (0, 255, 0), (0, 0, 255), (128, 64, 200), (0, 0, 0)] {
+            let (l, u, v) = rgb_to_luv(color);
+            let back = luv_to_rgb(l, u, v);
+            assert!((back.0 as i16 - color.0 as i16).abs() <= 1);
+            assert!((back.1 as i16 - color.1 as i16).abs() <= 1);
+            assert!((back.2 as i16 - color.2 as i16).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_lab_gradient_hits_endpoints() {
+        let gradient = ColorGradient::with_mode((255, 0, 0), (0, 0, 255), InterpolationMode::Lab, 3);
+        let colors: Vec<_> = gradient.collect();
+        assert_eq!(colors[0], (255, 0, 0));
+        assert_eq!(colors[2], (0, 0, 255));
+    }
+
+    #[test]
+    fn test_luv_gradient_hits_endpoints() {
+        let gradient = ColorGradient::with_mode((255, 0, 0), (0, 0, 255), InterpolationMode::Luv, 3);
+        let colors: Vec<_> = gradient.collect();
+        assert_eq!(colors[0], (255, 0, 0));
+        assert_eq!(colors[2], (0, 0, 255));
+    }
+}
@@ -0,0 +1,140 @@
+//! Parses `--start-color`/`--end-color` values in any of the forms a user
+//! might reach for: the original `R,G,B` decimal triple, `#RGB`/`#RRGGBB`
+//! hex, the CSS `rgb(...)`/`hsl(...)` functions, or a CSS named color.
+
+use crate::color_gradient::hsl_to_rgb;
+
+/// Parses a color in `R,G,B`, `#RGB`/`#RRGGBB`, `rgb(r,g,b)`, `hsl(h,s%,l%)`,
+/// or CSS named-color form (e.g. `skyblue`).
+pub fn parse_color(s: &str) -> Result<(u8, u8, u8), String> {
+    let s = s.trim();
+
+    if let Some(hex) = s.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some(inner) = s.strip_prefix("rgb(").and_then(|rest| rest.strip_suffix(')')) {
+        return parse_decimal_triple(inner);
+    }
+    if let Some(inner) = s.strip_prefix("hsl(").and_then(|rest| rest.strip_suffix(')')) {
+        return parse_hsl_function(inner);
+    }
+    if let Some(rgb) = named_color(s) {
+        return Ok(rgb);
+    }
+
+    parse_decimal_triple(s)
+}
+
+fn parse_decimal_triple(s: &str) -> Result<(u8, u8, u8), String> {
+    let parts: Vec<&str> = s.split(',').map(str::trim).collect();
+    if parts.len() != 3 {
+        return Err(format!("Color must be in R,G,B format, got: {}", s));
+    }
+
+    let r = parts[0]
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid red value: {}", parts[0]))?;
+    let g = parts[1]
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid green value: {}", parts[1]))?;
+    let b = parts[2]
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid blue value: {}", parts[2]))?;
+
+    Ok((r, g, b))
+}
+
+fn parse_hex(hex: &str) -> Result<(u8, u8, u8), String> {
+    let expand_digit = |c: char| u8::from_str_radix(&c.to_string().repeat(2), 16);
+
+    match hex.len() {
+        3 => {
+            let mut chars = hex.chars();
+            let err = || format!("Invalid hex color: #{}", hex);
+            let r = expand_digit(chars.next().unwrap()).map_err(|_| err())?;
+            let g = expand_digit(chars.next().unwrap()).map_err(|_| err())?;
+            let b = expand_digit(chars.next().unwrap()).map_err(|_| err())?;
+            Ok((r, g, b))
+        }
+        6 => {
+            let err = || format!("Invalid hex color: #{}", hex);
+            let r = u8::from_str_radix(&hex[0..2], 16).map_err(|_| err())?;
+            let g = u8::from_str_radix(&hex[2..4], 16).map_err(|_| err())?;
+            let b = u8::from_str_radix(&hex[4..6], 16).map_err(|_| err())?;
+            Ok((r, g, b))
+        }
+        _ => Err(format!("Hex color must be #RGB or #RRGGBB, got: #{}", hex)),
+    }
+}
+
+/// Parses the inside of `hsl(h,s%,l%)`: hue in degrees, saturation and
+/// lightness as percentages, converted via [`hsl_to_rgb`]'s chroma formula.
+fn parse_hsl_function(inner: &str) -> Result<(u8, u8, u8), String> {
+    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+    if parts.len() != 3 {
+        return Err(format!("hsl() must have 3 components, got: hsl({})", inner));
+    }
+
+    let h = parts[0]
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid hue: {}", parts[0]))?;
+    let s = parts[1]
+        .strip_suffix('%')
+        .unwrap_or(parts[1])
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid saturation: {}", parts[1]))?
+        / 100.0;
+    let l = parts[2]
+        .strip_suffix('%')
+        .unwrap_or(parts[2])
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid lightness: {}", parts[2]))?
+        / 100.0;
+
+    Ok(hsl_to_rgb(h.rem_euclid(360.0), s.clamp(0.0, 1.0), l.clamp(0.0, 1.0)))
+}
+
+/// A handful of the common CSS named colors.
+fn named_color(name: &str) -> Option<(u8, u8, u8)> {
+    let rgb = match name.to_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" => (0, 255, 255),
+        "magenta" => (255, 0, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "orange" => (255, 165, 0),
+        "purple" => (128, 0, 128),
+        "pink" => (255, 192, 203),
+        "brown" => (165, 42, 42),
+        "lime" => (0, 255, 0),
+        "navy" => (0, 0, 128),
+        "teal" => (0, 128, 128),
+        "olive" => (128, 128, 0),
+        "maroon" => (128, 0, 0),
+        "silver" => (192, 192, 192),
+        "gold" => (255, 215, 0),
+        "indigo" => (75, 0, 130),
+        "violet" => (238, 130, 238),
+        "coral" => (255, 127, 80),
+        "salmon" => (250, 128, 114),
+        "khaki" => (240, 230, 140),
+        "skyblue" => (135, 206, 235),
+        "steelblue" => (70, 130, 180),
+        "tomato" => (255, 99, 71),
+        "turquoise" => (64, 224, 208),
+        "crimson" => (220, 20, 60),
+        "chocolate" => (210, 105, 30),
+        "orchid" => (218, 112, 214),
+        "plum" => (221, 160, 221),
+        "ivory" => (255, 255, 240),
+        "beige" => (245, 245, 220),
+        "lavender" => (230, 230, 250),
+        _ => return None,
+    };
+
+    Some(rgb)
+}
@@ -0,0 +1,72 @@
+//! On-disk render presets, loaded via `--config` and merged underneath any
+//! explicit CLI flags (see `main::resolve_settings`). Mirrors benoit's
+//! `configuration/load.rs`: a `Config` with its own built-in defaults, read
+//! from TOML, merged over by whatever the user actually typed on the
+//! command line.
+
+use serde::Deserialize;
+
+use crate::{
+    DEFAULT_ANIMATION_OUTPUT, DEFAULT_CAMERA_RADIUS, DEFAULT_FOCAL_LENGTH, DEFAULT_FPS,
+    DEFAULT_FRAMES, DEFAULT_STEPS, DEFAULT_SVG_OUTPUT,
+};
+
+/// A render preset: the same fields as [`crate::Args`], each defaulting to
+/// the CLI's own built-in default so a config file only needs to list the
+/// fields it wants to override.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub steps: usize,
+    pub start_color: String,
+    pub end_color: String,
+    pub walk_type: String,
+    pub order: String,
+    pub color_space: String,
+    pub camera_radius: f64,
+    pub focal_length: f64,
+    pub animate: bool,
+    pub frames: usize,
+    pub fps: u32,
+    pub output: String,
+    pub oscillate_radius: bool,
+    pub format: String,
+    pub svg_output: String,
+    pub shade: bool,
+    pub smooth: bool,
+    pub projection: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            steps: DEFAULT_STEPS,
+            start_color: "255,0,0".to_string(),
+            end_color: "0,0,255".to_string(),
+            walk_type: "prime_walk".to_string(),
+            order: "sequential".to_string(),
+            color_space: "rgb".to_string(),
+            camera_radius: DEFAULT_CAMERA_RADIUS,
+            focal_length: DEFAULT_FOCAL_LENGTH,
+            animate: false,
+            frames: DEFAULT_FRAMES,
+            fps: DEFAULT_FPS,
+            output: DEFAULT_ANIMATION_OUTPUT.to_string(),
+            oscillate_radius: false,
+            format: "png".to_string(),
+            svg_output: DEFAULT_SVG_OUTPUT.to_string(),
+            shade: false,
+            smooth: false,
+            projection: "pinhole".to_string(),
+        }
+    }
+}
+
+/// Reads and parses the TOML config file at `path`, falling back to
+/// [`Config::default`] for any field it omits.
+pub fn load(path: &str) -> Result<Config, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file {}: {}", path, e))?;
+
+    toml::from_str(&contents).map_err(|e| format!("Failed to parse config file {}: {}", path, e))
+}
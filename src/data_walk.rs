@@ -1,10 +1,113 @@
 use crate::{
-    color_gradient::ColorGradient,
+    color_gradient::{ColorGradient, InterpolationMode},
     space::{Pixel3D, Tuple3D},
 };
 use std::fs;
 
-pub fn walk(_steps: usize, _gradient: ColorGradient, start_color: (u8, u8, u8), end_color: (u8, u8, u8)) -> Vec<Pixel3D> {
+/// How `data_walk` assigns each parsed data record a position. `Sequential`
+/// keeps the file's own `x,y,z` columns; `Morton`/`Hilbert` instead place
+/// record `i` along a space-filling curve over a cube grid, so records that
+/// are adjacent in the file stay spatially close in the render - the same
+/// locality-preserving trick kd-forest uses for laying out colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    Sequential,
+    Morton,
+    Hilbert,
+}
+
+/// Replaces each coordinate with its position along `order`'s space-filling
+/// curve, one grid cell per record index; `Sequential` is a no-op.
+fn apply_order(coordinates: Vec<Tuple3D>, order: Order) -> Vec<Tuple3D> {
+    if order == Order::Sequential || coordinates.len() <= 1 {
+        return coordinates;
+    }
+
+    let side = (coordinates.len() as f64).cbrt().ceil() as u32;
+    let bits = 32 - (side.max(1) - 1).leading_zeros().min(32);
+
+    (0..coordinates.len())
+        .map(|i| {
+            let (x, y, z) = match order {
+                Order::Morton => morton_position(i as u64, bits),
+                Order::Hilbert => hilbert_position(i as u64, bits),
+                Order::Sequential => unreachable!(),
+            };
+
+            Tuple3D {
+                x: x as f64,
+                y: y as f64,
+                z: z as f64,
+            }
+        })
+        .collect()
+}
+
+/// Converts a Morton (Z-order) index to `(x, y, z)` by de-interleaving its
+/// bits three at a time, one per axis.
+fn morton_position(index: u64, bits: u32) -> (u32, u32, u32) {
+    let mut x = 0u32;
+    let mut y = 0u32;
+    let mut z = 0u32;
+
+    for b in 0..bits {
+        x |= (((index >> (3 * b)) & 1) as u32) << b;
+        y |= (((index >> (3 * b + 1)) & 1) as u32) << b;
+        z |= (((index >> (3 * b + 2)) & 1) as u32) << b;
+    }
+
+    (x, y, z)
+}
+
+/// Converts a Hilbert-curve index to `(x, y, z)` on a `2^bits`-per-axis cube,
+/// via the standard transpose + Gray-code rotation transform.
+fn hilbert_position(index: u64, bits: u32) -> (u32, u32, u32) {
+    const DIMS: usize = 3;
+    let mut coords = [0u32; DIMS];
+
+    // Transpose: bit `b` of dimension `i` is bit `b * DIMS + (DIMS - 1 - i)` of
+    // the index - the axes are packed most-significant-dimension-first.
+    for b in 0..bits {
+        for (i, coord) in coords.iter_mut().enumerate() {
+            let axis = DIMS - 1 - i;
+            *coord |= (((index >> (b as u64 * DIMS as u64 + axis as u64)) & 1) as u32) << b;
+        }
+    }
+
+    // Gray decode.
+    let mut t = coords[DIMS - 1] >> 1;
+    for i in (1..DIMS).rev() {
+        coords[i] ^= coords[i - 1];
+    }
+    coords[0] ^= t;
+
+    // Undo the excess work from the Gray-code rotation at each scale.
+    let mut q: u32 = 2;
+    while bits > 0 && q != (1 << bits) {
+        let p = q - 1;
+        for i in (0..DIMS).rev() {
+            if coords[i] & q != 0 {
+                coords[0] ^= p;
+            } else {
+                t = (coords[0] ^ coords[i]) & p;
+                coords[0] ^= t;
+                coords[i] ^= t;
+            }
+        }
+        q <<= 1;
+    }
+
+    (coords[0], coords[1], coords[2])
+}
+
+pub fn walk(
+    _steps: usize,
+    _gradient: ColorGradient,
+    start_color: (u8, u8, u8),
+    end_color: (u8, u8, u8),
+    color_space: InterpolationMode,
+    order: Order,
+) -> Vec<Pixel3D> {
     // Read the data file
     let contents = fs::read_to_string("data")
         .expect("Failed to read data file");
@@ -35,9 +138,11 @@ pub fn walk(_steps: usize, _gradient: ColorGradient, start_color: (u8, u8, u8),
         coordinates.push(Tuple3D { x, y, z });
     }
 
+    let coordinates = apply_order(coordinates, order);
+
     // Create gradient with the correct number of steps based on actual data points
     let data_point_count = coordinates.len();
-    let mut gradient = ColorGradient::new(start_color, end_color, data_point_count);
+    let mut gradient = ColorGradient::with_mode(start_color, end_color, color_space, data_point_count);
 
     // Second pass: create pixels with gradient colors
     let mut result = vec![];
@@ -51,3 +156,120 @@ pub fn walk(_steps: usize, _gradient: ColorGradient, start_color: (u8, u8, u8),
 
     result
 }
+
+/// Data-parallel variant of [`walk`]. The second pass computes each pixel's color
+/// from its index via [`ColorGradient::color_at`] instead of the sequential
+/// iterator, so workers don't need to share gradient state.
+#[cfg(feature = "parallel")]
+pub fn walk_parallel(
+    _steps: usize,
+    _gradient: ColorGradient,
+    start_color: (u8, u8, u8),
+    end_color: (u8, u8, u8),
+    color_space: InterpolationMode,
+    order: Order,
+) -> Vec<Pixel3D> {
+    use rayon::prelude::*;
+
+    let contents = fs::read_to_string("data").expect("Failed to read data file");
+
+    let mut coordinates = vec![];
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() != 3 {
+            eprintln!("Invalid line format: {}", line);
+            continue;
+        }
+
+        let x = parts[0].trim().parse::<f64>()
+            .expect(&format!("Failed to parse X coordinate: {}", parts[0]));
+        let y = parts[1].trim().parse::<f64>()
+            .expect(&format!("Failed to parse Y coordinate: {}", parts[1]));
+        let z = parts[2].trim().parse::<f64>()
+            .expect(&format!("Failed to parse Z coordinate: {}", parts[2]));
+
+        coordinates.push(Tuple3D { x, y, z });
+    }
+
+    let coordinates = apply_order(coordinates, order);
+    let gradient = ColorGradient::with_mode(start_color, end_color, color_space, coordinates.len());
+
+    coordinates
+        .into_par_iter()
+        .enumerate()
+        .map(|(index, coordinate)| Pixel3D {
+            coordinate,
+            color: gradient.color_at(index),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morton_position_known_values() {
+        assert_eq!(morton_position(0, 2), (0, 0, 0));
+        assert_eq!(morton_position(1, 2), (1, 0, 0));
+        assert_eq!(morton_position(2, 2), (0, 1, 0));
+        assert_eq!(morton_position(3, 2), (1, 1, 0));
+        assert_eq!(morton_position(4, 2), (0, 0, 1));
+        assert_eq!(morton_position(5, 2), (1, 0, 1));
+    }
+
+    #[test]
+    fn test_morton_position_stays_within_grid() {
+        let bits = 4;
+        let side = 1u32 << bits;
+
+        for i in 0..(1u64 << (3 * bits)) {
+            let (x, y, z) = morton_position(i, bits);
+            assert!(x < side && y < side && z < side);
+        }
+    }
+
+    #[test]
+    fn test_hilbert_position_known_values() {
+        // Verified against the standard Skilling transpose/Gray-decode
+        // algorithm for a 2-bit-per-axis (4x4x4) cube.
+        assert_eq!(hilbert_position(0, 2), (0, 0, 0));
+        assert_eq!(hilbert_position(1, 2), (0, 1, 0));
+        assert_eq!(hilbert_position(2, 2), (1, 1, 0));
+        assert_eq!(hilbert_position(3, 2), (1, 0, 0));
+        assert_eq!(hilbert_position(4, 2), (1, 0, 1));
+        assert_eq!(hilbert_position(5, 2), (1, 1, 1));
+        assert_eq!(hilbert_position(6, 2), (0, 1, 1));
+        assert_eq!(hilbert_position(7, 2), (0, 0, 1));
+    }
+
+    #[test]
+    fn test_hilbert_position_consecutive_indices_are_adjacent_grid_cells() {
+        // The defining property of a space-filling curve: consecutive indices
+        // must map to adjacent grid cells, i.e. exactly one axis changes by
+        // exactly one step.
+        let bits = 4;
+
+        let mut prev = hilbert_position(0, bits);
+        for i in 1..(1u64 << (3 * bits)) {
+            let cur = hilbert_position(i, bits);
+            let manhattan_distance = (cur.0 as i64 - prev.0 as i64).abs()
+                + (cur.1 as i64 - prev.1 as i64).abs()
+                + (cur.2 as i64 - prev.2 as i64).abs();
+            assert_eq!(
+                manhattan_distance, 1,
+                "index {} -> {} jumped from {:?} to {:?}",
+                i - 1,
+                i,
+                prev,
+                cur
+            );
+            prev = cur;
+        }
+    }
+}
@@ -1,75 +1,424 @@
-use crate::color_gradient::ColorGradient;
+use crate::camera::ProjectionMode;
+use crate::color_gradient::{ColorGradient, InterpolationMode};
+use crate::color_parse::parse_color;
+use crate::data_walk::Order;
 use clap::Parser;
 
+mod animate;
 mod app;
 mod camera;
 mod color_gradient;
+mod color_parse;
+mod config;
 mod cube;
 mod data_walk;
 mod prime_walk;
 mod primes;
+#[cfg(test)]
+mod render;
+mod shading;
+mod smoothing;
 mod space;
+mod svg;
 
 pub const SIZE: usize = 800;
 const DEFAULT_STEPS: usize = 25_000;
 const DEFAULT_CAMERA_RADIUS: f64 = 600.0;
 const DEFAULT_FOCAL_LENGTH: f64 = 600.0;
+const DEFAULT_FRAMES: usize = 120;
+const DEFAULT_FPS: u32 = 30;
+const DEFAULT_ANIMATION_OUTPUT: &str = "frames";
+const DEFAULT_SVG_OUTPUT: &str = "walk.svg";
+const DEFAULT_SMOOTH_TOLERANCE: f64 = 0.5;
+
+/// How the walk is written out: rasterized (the interactive viewer, or
+/// `--animate`'s frame sequence/GIF) or as a single vector SVG document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Png,
+    Svg,
+}
 
 /// 3D Prime Walk - A mesmerizing visualization of prime numbers in 3D space
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    /// Load a TOML config file populating the fields below; any flag passed
+    /// explicitly on the command line overrides the corresponding file value
+    #[arg(long)]
+    config: Option<String>,
+
     /// Number of steps to take in the walk
-    #[arg(short = 'n', long, default_value_t = DEFAULT_STEPS)]
-    steps: usize,
+    #[arg(short = 'n', long)]
+    steps: Option<usize>,
 
-    /// Start color in R,G,B format (e.g., "255,0,0" for red)
-    #[arg(short = 's', long, value_parser = parse_color, default_value = "255,0,0")]
-    start_color: (u8, u8, u8),
+    /// Start color as "R,G,B" (e.g., "255,0,0"), "#RGB"/"#RRGGBB" hex,
+    /// "rgb(r,g,b)", "hsl(h,s%,l%)", or a CSS named color (e.g. "skyblue")
+    #[arg(short = 's', long, value_parser = parse_color)]
+    start_color: Option<(u8, u8, u8)>,
 
-    /// End color in R,G,B format (e.g., "0,0,255" for blue)
-    #[arg(short = 'e', long, value_parser = parse_color, default_value = "0,0,255")]
-    end_color: (u8, u8, u8),
+    /// End color, accepting the same forms as --start-color
+    #[arg(short = 'e', long, value_parser = parse_color)]
+    end_color: Option<(u8, u8, u8)>,
 
     /// Type of walk to generate (prime_walk or data_walk)
-    #[arg(short = 'w', long, default_value = "prime_walk")]
-    walk_type: String,
+    #[arg(short = 'w', long)]
+    walk_type: Option<String>,
+
+    /// How data_walk places records in space: sequential (use the file's own
+    /// x,y,z), morton, or hilbert (place record i along the curve, so data
+    /// adjacent in the file stays spatially close)
+    #[arg(long, value_parser = parse_order)]
+    order: Option<Order>,
+
+    /// Color space used to interpolate the gradient (rgb, hsl, lab, or luv)
+    #[arg(long, value_parser = parse_color_space)]
+    color_space: Option<InterpolationMode>,
+
+    /// Output format: png renders into the interactive viewer (or, with
+    /// --animate, a frame sequence or GIF); svg instead writes a single vector
+    /// document of the projected walk to --svg-output
+    #[arg(long, value_parser = parse_format)]
+    format: Option<OutputFormat>,
+
+    /// Output path for --format svg
+    #[arg(long)]
+    svg_output: Option<String>,
+
+    /// Distance from the walk the camera orbits at
+    #[arg(long)]
+    camera_radius: Option<f64>,
+
+    /// Camera focal length, controlling field of view
+    #[arg(long)]
+    focal_length: Option<f64>,
+
+    /// Render a turntable animation instead of opening the interactive viewer
+    #[arg(long)]
+    animate: bool,
+
+    /// Number of frames in the turntable sweep (only with --animate)
+    #[arg(long)]
+    frames: Option<usize>,
+
+    /// Playback speed of the output GIF, in frames per second (only with --animate)
+    #[arg(long)]
+    fps: Option<u32>,
+
+    /// Output path for --animate: a directory for a PNG frame sequence
+    /// (falling back to PPM without `--features png`), or a `.gif` file for
+    /// a single animated GIF
+    #[arg(long)]
+    output: Option<String>,
+
+    /// Oscillate the camera radius through one sine cycle over the sweep, for
+    /// a slight dolly in/out as the camera turns (only with --animate)
+    #[arg(long)]
+    oscillate_radius: bool,
+
+    /// Shade the walk with Phong lighting from a headlamp at the default
+    /// camera position, giving the curve a lit, solid appearance
+    #[arg(long)]
+    shade: bool,
+
+    /// Smooth the walk into a Catmull-Rom spline before rendering, turning
+    /// the blocky unit steps into a flowing curve
+    #[arg(long)]
+    smooth: bool,
+
+    /// How the camera maps 3D points onto the image: pinhole, equirectangular
+    /// (360-degree panorama), or fisheye. Also cyclable at runtime with the B
+    /// key in the interactive viewer
+    #[arg(long, value_parser = parse_projection_mode)]
+    projection: Option<ProjectionMode>,
 }
 
-fn parse_color(s: &str) -> Result<(u8, u8, u8), String> {
-    let parts: Vec<&str> = s.split(',').collect();
-    if parts.len() != 3 {
-        return Err(format!("Color must be in R,G,B format, got: {}", s));
+fn parse_color_space(s: &str) -> Result<InterpolationMode, String> {
+    match s.to_lowercase().as_str() {
+        "rgb" => Ok(InterpolationMode::Rgb),
+        "hsl" => Ok(InterpolationMode::Hsl),
+        "lab" => Ok(InterpolationMode::Lab),
+        "luv" => Ok(InterpolationMode::Luv),
+        _ => Err(format!(
+            "Color space must be one of rgb, hsl, lab, luv, got: {}",
+            s
+        )),
     }
+}
 
-    let r = parts[0]
-        .parse::<u8>()
-        .map_err(|_| format!("Invalid red value: {}", parts[0]))?;
-    let g = parts[1]
-        .parse::<u8>()
-        .map_err(|_| format!("Invalid green value: {}", parts[1]))?;
-    let b = parts[2]
-        .parse::<u8>()
-        .map_err(|_| format!("Invalid blue value: {}", parts[2]))?;
+fn parse_format(s: &str) -> Result<OutputFormat, String> {
+    match s.to_lowercase().as_str() {
+        "png" => Ok(OutputFormat::Png),
+        "svg" => Ok(OutputFormat::Svg),
+        _ => Err(format!("Format must be one of png, svg, got: {}", s)),
+    }
+}
 
-    Ok((r, g, b))
+fn parse_projection_mode(s: &str) -> Result<ProjectionMode, String> {
+    match s.to_lowercase().as_str() {
+        "pinhole" => Ok(ProjectionMode::Pinhole),
+        "equirectangular" => Ok(ProjectionMode::Equirectangular),
+        "fisheye" => Ok(ProjectionMode::Fisheye),
+        _ => Err(format!(
+            "Projection must be one of pinhole, equirectangular, fisheye, got: {}",
+            s
+        )),
+    }
+}
+
+fn parse_order(s: &str) -> Result<Order, String> {
+    match s.to_lowercase().as_str() {
+        "sequential" => Ok(Order::Sequential),
+        "morton" => Ok(Order::Morton),
+        "hilbert" => Ok(Order::Hilbert),
+        _ => Err(format!(
+            "Order must be one of sequential, morton, hilbert, got: {}",
+            s
+        )),
+    }
+}
+
+/// The fully resolved set of settings a run actually uses: `args`'s explicit
+/// flags overlaid on top of `config` (itself defaulted from the CLI's own
+/// built-in defaults when no `--config` is given).
+struct Settings {
+    steps: usize,
+    start_color: (u8, u8, u8),
+    end_color: (u8, u8, u8),
+    walk_type: String,
+    order: Order,
+    color_space: InterpolationMode,
+    camera_radius: f64,
+    focal_length: f64,
+    animate: bool,
+    frames: usize,
+    fps: u32,
+    output: String,
+    oscillate_radius: bool,
+    format: OutputFormat,
+    svg_output: String,
+    shade: bool,
+    smooth: bool,
+    projection_mode: ProjectionMode,
+}
+
+fn resolve_settings(args: Args) -> Settings {
+    let config = match &args.config {
+        Some(path) => config::load(path).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }),
+        None => config::Config::default(),
+    };
+
+    Settings {
+        steps: args.steps.unwrap_or(config.steps),
+        start_color: args
+            .start_color
+            .unwrap_or_else(|| parse_color(&config.start_color).expect("Invalid start_color in config")),
+        end_color: args
+            .end_color
+            .unwrap_or_else(|| parse_color(&config.end_color).expect("Invalid end_color in config")),
+        walk_type: args.walk_type.unwrap_or(config.walk_type),
+        order: args
+            .order
+            .unwrap_or_else(|| parse_order(&config.order).expect("Invalid order in config")),
+        color_space: args.color_space.unwrap_or_else(|| {
+            parse_color_space(&config.color_space).expect("Invalid color_space in config")
+        }),
+        camera_radius: args.camera_radius.unwrap_or(config.camera_radius),
+        focal_length: args.focal_length.unwrap_or(config.focal_length),
+        animate: args.animate || config.animate,
+        frames: args.frames.unwrap_or(config.frames),
+        fps: args.fps.unwrap_or(config.fps),
+        output: args.output.unwrap_or(config.output),
+        oscillate_radius: args.oscillate_radius || config.oscillate_radius,
+        format: args
+            .format
+            .unwrap_or_else(|| parse_format(&config.format).expect("Invalid format in config")),
+        svg_output: args.svg_output.unwrap_or(config.svg_output),
+        shade: args.shade || config.shade,
+        smooth: args.smooth || config.smooth,
+        projection_mode: args.projection.unwrap_or_else(|| {
+            parse_projection_mode(&config.projection).expect("Invalid projection in config")
+        }),
+    }
 }
 
 fn main() {
     let args = Args::parse();
+    let settings = resolve_settings(args);
 
     // Warn if steps argument is used with data_walk mode
-    if args.walk_type == "data_walk" && args.steps != DEFAULT_STEPS {
+    if settings.walk_type == "data_walk" && settings.steps != DEFAULT_STEPS {
         eprintln!("Warning: --steps/-n argument is ignored in data_walk mode. The number of points is determined by the data file.");
     }
 
-    let gradient = ColorGradient::new(args.start_color, args.end_color, args.steps);
+    let gradient = ColorGradient::with_mode(
+        settings.start_color,
+        settings.end_color,
+        settings.color_space,
+        settings.steps,
+    );
 
-    let pixels = match args.walk_type.as_str() {
-        "cube" => cube::walk(args.steps, gradient),
-        "data_walk" => data_walk::walk(args.steps, gradient, args.start_color, args.end_color),
-        _ => prime_walk::walk(args.steps, gradient),
+    let mut pixels = match settings.walk_type.as_str() {
+        "cube" => cube::walk(settings.steps, gradient),
+        "data_walk" => run_data_walk(
+            settings.steps,
+            gradient,
+            settings.start_color,
+            settings.end_color,
+            settings.color_space,
+            settings.order,
+        ),
+        _ => prime_walk::walk(settings.steps, gradient),
     };
 
-    app::image(pixels, DEFAULT_CAMERA_RADIUS, DEFAULT_FOCAL_LENGTH);
+    if settings.smooth {
+        pixels = smoothing::smooth(
+            &pixels,
+            DEFAULT_SMOOTH_TOLERANCE,
+            settings.start_color,
+            settings.end_color,
+        );
+    }
+
+    if settings.shade {
+        shade_pixels(&mut pixels, &settings);
+    }
+
+    if settings.format == OutputFormat::Svg {
+        render_svg(&pixels, &settings);
+        return;
+    }
+
+    if settings.animate {
+        render_animation(&pixels, &settings);
+        return;
+    }
+
+    app::image(
+        pixels,
+        settings.camera_radius,
+        settings.focal_length,
+        settings.projection_mode,
+    );
+}
+
+/// Dispatches to [`data_walk::walk_parallel`] when built with `--features
+/// parallel`, and to the sequential [`data_walk::walk`] otherwise.
+#[cfg(feature = "parallel")]
+fn run_data_walk(
+    steps: usize,
+    gradient: ColorGradient,
+    start_color: (u8, u8, u8),
+    end_color: (u8, u8, u8),
+    color_space: InterpolationMode,
+    order: Order,
+) -> Vec<space::Pixel3D> {
+    data_walk::walk_parallel(steps, gradient, start_color, end_color, color_space, order)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn run_data_walk(
+    steps: usize,
+    gradient: ColorGradient,
+    start_color: (u8, u8, u8),
+    end_color: (u8, u8, u8),
+    color_space: InterpolationMode,
+    order: Order,
+) -> Vec<space::Pixel3D> {
+    data_walk::walk(steps, gradient, start_color, end_color, color_space, order)
+}
+
+/// Shades `pixels` in place with Phong lighting from a headlamp positioned at
+/// the default camera eye (`(0, 0, camera_radius)`, the orbit's identity
+/// orientation), multiplying the gradient color by the lighting result.
+/// Dispatches to [`shading::shade_walk_parallel`] when built with `--features
+/// parallel`.
+#[cfg(feature = "parallel")]
+fn shade_pixels(pixels: &mut [space::Pixel3D], settings: &Settings) {
+    let eye = space::Tuple3D {
+        x: 0.0,
+        y: 0.0,
+        z: settings.camera_radius,
+    };
+    let light = shading::Light {
+        position: eye,
+        intensity: (1.0, 1.0, 1.0),
+    };
+
+    shading::shade_walk_parallel(pixels, &shading::Material::default(), &light, &eye);
+}
+
+#[cfg(not(feature = "parallel"))]
+fn shade_pixels(pixels: &mut [space::Pixel3D], settings: &Settings) {
+    let eye = space::Tuple3D {
+        x: 0.0,
+        y: 0.0,
+        z: settings.camera_radius,
+    };
+    let light = shading::Light {
+        position: eye,
+        intensity: (1.0, 1.0, 1.0),
+    };
+
+    shading::shade_walk(pixels, &shading::Material::default(), &light, &eye);
+}
+
+/// Projects `pixels` through a stationary default orbit and writes the
+/// result as a single SVG document, bypassing both the interactive viewer
+/// and `--animate`'s turntable sweep.
+fn render_svg(pixels: &[space::Pixel3D], settings: &Settings) {
+    let mut orbit = camera::Orbit::new(settings.camera_radius, settings.focal_length, SIZE, SIZE);
+    orbit.set_projection_mode(settings.projection_mode);
+
+    svg::write_svg(pixels, &orbit, &settings.svg_output).expect("Failed to write SVG file");
+}
+
+fn render_animation(pixels: &[space::Pixel3D], settings: &Settings) {
+    let params = animate::TurntableParams {
+        frames: settings.frames,
+        camera_radius: settings.camera_radius,
+        focal_length: settings.focal_length,
+        oscillate_radius: settings.oscillate_radius,
+        width: SIZE,
+        height: SIZE,
+        projection_mode: settings.projection_mode,
+    };
+    let frames = animate::render_turntable(pixels, &params);
+
+    if settings.output.ends_with(".gif") {
+        write_gif_or_fallback(&frames, &settings.output, settings.fps);
+    } else {
+        write_frames_or_fallback(&frames, &settings.output);
+    }
+}
+
+#[cfg(feature = "png")]
+fn write_frames_or_fallback(frames: &[eframe::egui::ColorImage], output_dir: &str) {
+    animate::write_frame_sequence_png(frames, output_dir).expect("Failed to write frame sequence");
+}
+
+#[cfg(not(feature = "png"))]
+fn write_frames_or_fallback(frames: &[eframe::egui::ColorImage], output_dir: &str) {
+    eprintln!(
+        "PNG frame output requires building with `--features png`; writing a PPM frame sequence to {} instead.",
+        output_dir
+    );
+    animate::write_frame_sequence(frames, output_dir).expect("Failed to write frame sequence");
+}
+
+#[cfg(feature = "gif")]
+fn write_gif_or_fallback(frames: &[eframe::egui::ColorImage], path: &str, fps: u32) {
+    animate::write_gif(frames, path, fps).expect("Failed to write GIF");
+}
+
+#[cfg(not(feature = "gif"))]
+fn write_gif_or_fallback(frames: &[eframe::egui::ColorImage], path: &str, _fps: u32) {
+    eprintln!(
+        "GIF output requires building with `--features gif`; writing a PPM frame sequence to {} instead.",
+        path
+    );
+    animate::write_frame_sequence(frames, path).expect("Failed to write frame sequence");
 }
@@ -0,0 +1,295 @@
+use crate::space::{Pixel3D, Tuple3D};
+
+/// A pinhole camera used to project 3D points onto a 2D canvas.
+pub struct Camera {
+    pub eye: Tuple3D,
+    pub look_at: Tuple3D,
+    pub up: Tuple3D,
+    pub fov: f64,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Camera {
+    pub fn new(
+        eye: Tuple3D,
+        look_at: Tuple3D,
+        up: Tuple3D,
+        fov: f64,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        Self {
+            eye,
+            look_at,
+            up,
+            fov,
+            width,
+            height,
+        }
+    }
+
+    /// Builds the orthonormal camera basis (forward, right, true_up).
+    fn basis(&self) -> (Tuple3D, Tuple3D, Tuple3D) {
+        let forward = normalize(&self.look_at.sub(&self.eye));
+        let right = normalize(&forward.cross(&self.up));
+        let true_up = right.cross(&forward);
+
+        (forward, right, true_up)
+    }
+
+    /// Projects a world point into pixel coordinates, if it lies in front of the camera.
+    fn project(&self, point: &Tuple3D) -> Option<(usize, usize)> {
+        let (forward, right, true_up) = self.basis();
+        let half_fov = (self.fov / 2.0).tan();
+
+        let to_point = point.sub(&self.eye);
+        let depth = to_point.dot(&forward);
+        if depth <= 0.0 {
+            return None;
+        }
+
+        // `fov` is the horizontal field of view; `aspect` derives the vertical
+        // extent from it so a non-square canvas doesn't stretch the image.
+        let aspect = self.width as f64 / self.height as f64;
+        let screen_x = to_point.dot(&right) / (depth * half_fov);
+        let screen_y = to_point.dot(&true_up) / (depth * half_fov / aspect);
+
+        // Normalized screen space is [-1, 1]; map to pixel indices.
+        let pixel_x = ((screen_x + 1.0) * 0.5 * self.width as f64).round();
+        let pixel_y = ((1.0 - screen_y) * 0.5 * self.height as f64).round();
+
+        if pixel_x < 0.0
+            || pixel_x >= self.width as f64
+            || pixel_y < 0.0
+            || pixel_y >= self.height as f64
+        {
+            return None;
+        }
+
+        Some((pixel_x as usize, pixel_y as usize))
+    }
+
+    /// Projects every pixel of a walk onto a fresh canvas.
+    pub fn render(&self, pixels: &[Pixel3D]) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+
+        for pixel in pixels {
+            if let Some((x, y)) = self.project(&pixel.coordinate) {
+                canvas.write_pixel(x, y, pixel.color);
+            }
+        }
+
+        canvas
+    }
+
+    /// Data-parallel variant of [`Camera::render`]: projection is the expensive part
+    /// for million-point walks, so it runs over `par_iter` and the results are
+    /// written into the canvas sequentially.
+    #[cfg(feature = "parallel")]
+    pub fn render_parallel(&self, pixels: &[Pixel3D]) -> Canvas {
+        use rayon::prelude::*;
+
+        let mut canvas = Canvas::new(self.width, self.height);
+
+        let projected: Vec<Option<((usize, usize), (u8, u8, u8))>> = pixels
+            .par_iter()
+            .map(|pixel| {
+                self.project(&pixel.coordinate)
+                    .map(|xy| (xy, pixel.color))
+            })
+            .collect();
+
+        for (xy, color) in projected.into_iter().flatten() {
+            canvas.write_pixel(xy.0, xy.1, color);
+        }
+
+        canvas
+    }
+}
+
+fn normalize(v: &Tuple3D) -> Tuple3D {
+    let length = (v.dot(v)).sqrt();
+    v.scale(1.0 / length)
+}
+
+/// A `width * height` buffer of RGB pixels that can be serialized to a PPM image.
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<(u8, u8, u8)>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![(0, 0, 0); width * height],
+        }
+    }
+
+    pub fn write_pixel(&mut self, x: usize, y: usize, color: (u8, u8, u8)) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    pub fn pixel_at(&self, x: usize, y: usize) -> (u8, u8, u8) {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Writes the canvas as an ASCII (P3) PPM image.
+    pub fn to_ppm(&self) -> String {
+        let mut ppm = format!("P3\n{} {}\n255\n", self.width, self.height);
+
+        for row in self.pixels.chunks(self.width) {
+            let line = row
+                .iter()
+                .map(|(r, g, b)| format!("{} {} {}", r, g, b))
+                .collect::<Vec<_>>()
+                .join(" ");
+            ppm.push_str(&line);
+            ppm.push('\n');
+        }
+
+        ppm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canvas_starts_black() {
+        let canvas = Canvas::new(10, 20);
+        assert_eq!(canvas.pixel_at(5, 5), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_write_pixel() {
+        let mut canvas = Canvas::new(10, 20);
+        canvas.write_pixel(2, 3, (255, 0, 0));
+        assert_eq!(canvas.pixel_at(2, 3), (255, 0, 0));
+    }
+
+    #[test]
+    fn test_to_ppm_header() {
+        let canvas = Canvas::new(5, 3);
+        let ppm = canvas.to_ppm();
+        assert!(ppm.starts_with("P3\n5 3\n255\n"));
+    }
+
+    #[test]
+    fn test_camera_projects_point_in_front() {
+        let camera = Camera::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            std::f64::consts::FRAC_PI_2,
+            100,
+            100,
+        );
+
+        let pixels = vec![Pixel3D {
+            coordinate: Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            color: (255, 255, 255),
+        }];
+
+        let canvas = camera.render(&pixels);
+        assert_eq!(canvas.pixel_at(50, 50), (255, 255, 255));
+    }
+
+    #[test]
+    fn test_camera_ignores_point_behind() {
+        let camera = Camera::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            std::f64::consts::FRAC_PI_2,
+            100,
+            100,
+        );
+
+        let pixels = vec![Pixel3D {
+            coordinate: Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: -10.0,
+            },
+            color: (255, 255, 255),
+        }];
+
+        let canvas = camera.render(&pixels);
+        assert_eq!(canvas.pixel_at(50, 50), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_camera_keeps_horizontal_fov_on_a_wide_viewport() {
+        // 2:1 viewport: `fov` is the horizontal field of view, so a point
+        // halfway to the horizontal edge of the view lands halfway between
+        // the center and the edge (x = 50 of 200), just as it would on a
+        // square viewport; only the vertical extent shrinks with the aspect.
+        let camera = Camera::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: -5.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            std::f64::consts::FRAC_PI_2,
+            200,
+            100,
+        );
+
+        let pixels = vec![Pixel3D {
+            coordinate: Tuple3D {
+                x: 2.5,
+                y: 0.0,
+                z: 0.0,
+            },
+            color: (255, 255, 255),
+        }];
+
+        let canvas = camera.render(&pixels);
+        assert_eq!(canvas.pixel_at(50, 50), (255, 255, 255));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::space::{Pixel3D, Tuple3D};
+
+/// A point light source with a position and an RGB intensity in `0.0..=1.0`.
+pub struct Light {
+    pub position: Tuple3D,
+    pub intensity: (f64, f64, f64),
+}
+
+/// Surface reflectance properties for the Phong model.
+#[derive(Debug, Copy, Clone)]
+pub struct Material {
+    pub ambient: f64,
+    pub diffuse: f64,
+    pub specular: f64,
+    pub shininess: f64,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+        }
+    }
+}
+
+fn normalize(v: &Tuple3D) -> Tuple3D {
+    let length = v.dot(v).sqrt();
+    if length < 1e-10 {
+        return *v;
+    }
+    v.scale(1.0 / length)
+}
+
+fn reflect(incoming: &Tuple3D, normal: &Tuple3D) -> Tuple3D {
+    incoming.sub(&normal.scale(2.0 * incoming.dot(normal)))
+}
+
+fn clamp_channel(value: f64) -> u8 {
+    value.clamp(0.0, 255.0).round() as u8
+}
+
+/// Computes the Phong-shaded color of `point` as seen by `eye` under `light`.
+pub fn lighting(
+    material: &Material,
+    light: &Light,
+    point: &Tuple3D,
+    eye: &Tuple3D,
+    normal: &Tuple3D,
+) -> (u8, u8, u8) {
+    let eyev = normalize(&eye.sub(point));
+    let lightv = normalize(&light.position.sub(point));
+    let normal = normalize(normal);
+
+    let (ir, ig, ib) = light.intensity;
+    let ambient = (ir * material.ambient, ig * material.ambient, ib * material.ambient);
+
+    let light_dot_normal = lightv.dot(&normal);
+    if light_dot_normal < 0.0 {
+        return (
+            clamp_channel(ambient.0 * 255.0),
+            clamp_channel(ambient.1 * 255.0),
+            clamp_channel(ambient.2 * 255.0),
+        );
+    }
+
+    let diffuse_factor = material.diffuse * light_dot_normal;
+    let diffuse = (ir * diffuse_factor, ig * diffuse_factor, ib * diffuse_factor);
+
+    let reflectv = reflect(&lightv.scale(-1.0), &normal);
+    let reflect_dot_eye = reflectv.dot(&eyev);
+
+    let specular = if reflect_dot_eye <= 0.0 {
+        (0.0, 0.0, 0.0)
+    } else {
+        let factor = material.specular * reflect_dot_eye.powf(material.shininess);
+        (ir * factor, ig * factor, ib * factor)
+    };
+
+    (
+        clamp_channel((ambient.0 + diffuse.0 + specular.0) * 255.0),
+        clamp_channel((ambient.1 + diffuse.1 + specular.1) * 255.0),
+        clamp_channel((ambient.2 + diffuse.2 + specular.2) * 255.0),
+    )
+}
+
+/// Derives a per-segment normal from the incoming and outgoing direction vectors,
+/// falling back to a fixed up-vector when the segment is colinear.
+fn segment_normal(incoming: &Tuple3D, outgoing: &Tuple3D) -> Tuple3D {
+    let cross = incoming.cross(outgoing);
+    if cross.dot(&cross) < 1e-10 {
+        return Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+    }
+
+    normalize(&cross)
+}
+
+fn multiply_channel_wise(color: (u8, u8, u8), light: (u8, u8, u8)) -> (u8, u8, u8) {
+    let scale = |c: u8, l: u8| ((c as f64 * l as f64) / 255.0).round() as u8;
+    (
+        scale(color.0, light.0),
+        scale(color.1, light.1),
+        scale(color.2, light.2),
+    )
+}
+
+/// Shades each walk point by multiplying its gradient color with the Phong lighting
+/// computed from its per-segment normal.
+pub fn shade_walk(pixels: &mut [Pixel3D], material: &Material, light: &Light, eye: &Tuple3D) {
+    let len = pixels.len();
+    for i in 0..len {
+        let point = pixels[i].coordinate;
+
+        let incoming = if i > 0 {
+            point.sub(&pixels[i - 1].coordinate)
+        } else if i + 1 < len {
+            pixels[i + 1].coordinate.sub(&point)
+        } else {
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            }
+        };
+
+        let outgoing = if i + 1 < len {
+            pixels[i + 1].coordinate.sub(&point)
+        } else {
+            incoming
+        };
+
+        let normal = segment_normal(&incoming, &outgoing);
+        let lit = lighting(material, light, &point, eye, &normal);
+        pixels[i].color = multiply_channel_wise(pixels[i].color, lit);
+    }
+}
+
+/// Data-parallel variant of [`shade_walk`] for large walks. Each worker reads its
+/// own and its neighbours' original coordinates, so shading stays consistent with
+/// the sequential pass.
+#[cfg(feature = "parallel")]
+pub fn shade_walk_parallel(pixels: &mut [Pixel3D], material: &Material, light: &Light, eye: &Tuple3D) {
+    use rayon::prelude::*;
+
+    let coordinates: Vec<Tuple3D> = pixels.iter().map(|p| p.coordinate).collect();
+    let len = coordinates.len();
+
+    pixels.par_iter_mut().enumerate().for_each(|(i, pixel)| {
+        let point = coordinates[i];
+
+        let incoming = if i > 0 {
+            point.sub(&coordinates[i - 1])
+        } else if i + 1 < len {
+            coordinates[i + 1].sub(&point)
+        } else {
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            }
+        };
+
+        let outgoing = if i + 1 < len {
+            coordinates[i + 1].sub(&point)
+        } else {
+            incoming
+        };
+
+        let normal = segment_normal(&incoming, &outgoing);
+        let lit = lighting(material, light, &point, eye, &normal);
+        pixel.color = multiply_channel_wise(pixel.color, lit);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_light() -> Light {
+        Light {
+            position: Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: -10.0,
+            },
+            intensity: (1.0, 1.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn test_lighting_eye_between_light_and_surface() {
+        let material = Material::default();
+        let point = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let eye = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let normal = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+
+        let result = lighting(&material, &default_light(), &point, &eye, &normal);
+        assert_eq!(result, (255, 255, 255));
+    }
+
+    #[test]
+    fn test_lighting_light_behind_surface() {
+        let material = Material::default();
+        let point = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let eye = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let normal = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let light = Light {
+            position: Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 10.0,
+            },
+            intensity: (1.0, 1.0, 1.0),
+        };
+
+        let result = lighting(&material, &light, &point, &eye, &normal);
+        let expected = (material.ambient * 255.0).round() as u8;
+        assert_eq!(result, (expected, expected, expected));
+    }
+
+    #[test]
+    fn test_segment_normal_colinear_falls_back() {
+        let incoming = Tuple3D {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let outgoing = Tuple3D {
+            x: 2.0,
+            y: 0.0,
+            z: 0.0,
+        };
+
+        let normal = segment_normal(&incoming, &outgoing);
+        assert_eq!((normal.x, normal.y, normal.z), (0.0, 1.0, 0.0));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    color_gradient::ColorGradient,
+    space::{Pixel3D, Tuple3D},
+};
+
+/// Evaluates the Catmull-Rom curve between `p1` and `p2` at parameter `u` in `0.0..=1.0`.
+fn catmull_rom(p0: &Tuple3D, p1: &Tuple3D, p2: &Tuple3D, p3: &Tuple3D, u: f64) -> Tuple3D {
+    let a = p1.scale(2.0);
+    let b = p2.sub(p0).scale(u);
+    let c = p0
+        .scale(2.0)
+        .sub(&p1.scale(5.0))
+        .add(&p2.scale(4.0))
+        .sub(p3)
+        .scale(u * u);
+    let d = p1
+        .scale(3.0)
+        .sub(p0)
+        .sub(&p2.scale(3.0))
+        .add(p3)
+        .scale(u * u * u);
+
+    a.add(&b).add(&c).add(&d).scale(0.5)
+}
+
+/// Distance from `point` to the straight chord `from -> to`.
+fn distance_to_chord(point: &Tuple3D, from: &Tuple3D, to: &Tuple3D) -> f64 {
+    let chord = to.sub(from);
+    let chord_len_sq = chord.dot(&chord);
+    if chord_len_sq < 1e-10 {
+        return point.coordinate_squared_distance(from).sqrt();
+    }
+
+    let t = (point.sub(from).dot(&chord) / chord_len_sq).clamp(0.0, 1.0);
+    let closest = from.add(&chord.scale(t));
+    point.coordinate_squared_distance(&closest).sqrt()
+}
+
+/// Recursively subdivides the `[u0, u1]` range of a Catmull-Rom segment until the
+/// midpoint deviation from the straight chord is under `tolerance`.
+fn flatten_segment(
+    p0: &Tuple3D,
+    p1: &Tuple3D,
+    p2: &Tuple3D,
+    p3: &Tuple3D,
+    u0: f64,
+    u1: f64,
+    tolerance: f64,
+    out: &mut Vec<Tuple3D>,
+) {
+    let start = catmull_rom(p0, p1, p2, p3, u0);
+    let end = catmull_rom(p0, p1, p2, p3, u1);
+    let mid_u = (u0 + u1) / 2.0;
+    let mid = catmull_rom(p0, p1, p2, p3, mid_u);
+
+    if distance_to_chord(&mid, &start, &end) <= tolerance {
+        out.push(end);
+        return;
+    }
+
+    flatten_segment(p0, p1, p2, p3, u0, mid_u, tolerance, out);
+    flatten_segment(p0, p1, p2, p3, mid_u, u1, tolerance, out);
+}
+
+/// Treats `coordinates` as Catmull-Rom control points and returns a denser poly-line
+/// whose deviation from the true curve is within `tolerance`.
+fn smooth_coordinates(coordinates: &[Tuple3D], tolerance: f64) -> Vec<Tuple3D> {
+    if coordinates.len() < 4 {
+        return coordinates.to_vec();
+    }
+
+    let mut result = vec![coordinates[0]];
+
+    for i in 0..coordinates.len() - 1 {
+        let p0 = coordinates[i.saturating_sub(1)];
+        let p1 = coordinates[i];
+        let p2 = coordinates[i + 1];
+        let p3 = coordinates[(i + 2).min(coordinates.len() - 1)];
+
+        flatten_segment(&p0, &p1, &p2, &p3, 0.0, 1.0, tolerance, &mut result);
+    }
+
+    result
+}
+
+/// Smooths a walk's points into a Catmull-Rom spline, re-running the gradient
+/// across the newly densified point count so colors stay continuous.
+pub fn smooth(
+    pixels: &[Pixel3D],
+    tolerance: f64,
+    start_color: (u8, u8, u8),
+    end_color: (u8, u8, u8),
+) -> Vec<Pixel3D> {
+    let coordinates: Vec<Tuple3D> = pixels.iter().map(|p| p.coordinate).collect();
+    let smoothed = smooth_coordinates(&coordinates, tolerance);
+
+    let mut gradient = ColorGradient::new(start_color, end_color, smoothed.len());
+
+    smoothed
+        .into_iter()
+        .map(|coordinate| Pixel3D {
+            coordinate,
+            color: gradient.next().unwrap(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn straight_line() -> Vec<Tuple3D> {
+        (0..5)
+            .map(|i| Tuple3D {
+                x: i as f64,
+                y: 0.0,
+                z: 0.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_smooth_coordinates_keeps_short_input_unchanged() {
+        let coordinates = vec![
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        ];
+
+        let result = smooth_coordinates(&coordinates, 0.01);
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_smooth_coordinates_does_not_drop_points_of_a_line() {
+        let result = smooth_coordinates(&straight_line(), 0.01);
+        assert!(result.len() >= straight_line().len());
+    }
+
+    #[test]
+    fn test_smooth_coordinates_stays_close_to_a_straight_line() {
+        let result = smooth_coordinates(&straight_line(), 0.01);
+        for point in &result {
+            assert!((point.y).abs() < 1e-6);
+            assert!((point.z).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_smooth_reruns_gradient_over_new_point_count() {
+        let pixels: Vec<Pixel3D> = straight_line()
+            .into_iter()
+            .map(|coordinate| Pixel3D {
+                coordinate,
+                color: (0, 0, 0),
+            })
+            .collect();
+
+        let smoothed = smooth(&pixels, 0.01, (255, 0, 0), (0, 0, 255));
+        assert_eq!(smoothed.first().unwrap().color, (255, 0, 0));
+        assert_eq!(smoothed.last().unwrap().color, (0, 0, 255));
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Tuple3D {
     pub x: f64,
     pub y: f64,
@@ -56,6 +56,261 @@ pub struct Pixel3D {
     pub color: (u8, u8, u8),
 }
 
+/// A row-major 4x4 matrix used to translate, scale and rotate a walk's points.
+///
+/// Only exercised by its own tests and [`apply_transform`]/[`apply_transform_parallel`]
+/// below - no pipeline stage calls them yet - so the whole group is gated behind
+/// `#[cfg(test)]` to keep a plain `cargo build`/`clippy` free of dead-code warnings.
+#[cfg(test)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Matrix4 {
+    rows: [[f64; 4]; 4],
+}
+
+#[cfg(test)]
+impl Matrix4 {
+    pub fn identity() -> Self {
+        Self {
+            rows: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    pub fn translation(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            rows: [
+                [1.0, 0.0, 0.0, x],
+                [0.0, 1.0, 0.0, y],
+                [0.0, 0.0, 1.0, z],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    pub fn scaling(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            rows: [
+                [x, 0.0, 0.0, 0.0],
+                [0.0, y, 0.0, 0.0],
+                [0.0, 0.0, z, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    pub fn rotation_x(radians: f64) -> Self {
+        let (s, c) = (radians.sin(), radians.cos());
+        Self {
+            rows: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, c, -s, 0.0],
+                [0.0, s, c, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    pub fn rotation_y(radians: f64) -> Self {
+        let (s, c) = (radians.sin(), radians.cos());
+        Self {
+            rows: [
+                [c, 0.0, s, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [-s, 0.0, c, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    pub fn rotation_z(radians: f64) -> Self {
+        let (s, c) = (radians.sin(), radians.cos());
+        Self {
+            rows: [
+                [c, -s, 0.0, 0.0],
+                [s, c, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// Transforms a point, treating it as `(x, y, z, 1)` and dividing out `w`.
+    pub fn transform_point(&self, point: &Tuple3D) -> Tuple3D {
+        let v = [point.x, point.y, point.z, 1.0];
+        let mut out = [0.0; 4];
+
+        for (row, out_component) in self.rows.iter().zip(out.iter_mut()) {
+            *out_component = row[0] * v[0] + row[1] * v[1] + row[2] * v[2] + row[3] * v[3];
+        }
+
+        Tuple3D {
+            x: out[0] / out[3],
+            y: out[1] / out[3],
+            z: out[2] / out[3],
+        }
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Mul for Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, other: Matrix4) -> Matrix4 {
+        let mut rows = [[0.0; 4]; 4];
+
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..4).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+        }
+
+        Matrix4 { rows }
+    }
+}
+
+/// Re-orients an entire walk in place by applying `m` to every coordinate.
+#[cfg(test)]
+pub fn apply_transform(pixels: &mut [Pixel3D], m: &Matrix4) {
+    for pixel in pixels.iter_mut() {
+        pixel.coordinate = m.transform_point(&pixel.coordinate);
+    }
+}
+
+/// Data-parallel variant of [`apply_transform`] for large walks.
+#[cfg(all(test, feature = "parallel"))]
+pub fn apply_transform_parallel(pixels: &mut [Pixel3D], m: &Matrix4) {
+    use rayon::prelude::*;
+
+    pixels.par_iter_mut().for_each(|pixel| {
+        pixel.coordinate = m.transform_point(&pixel.coordinate);
+    });
+}
+
+/// A unit quaternion used for camera orientation. Unlike a triple of Euler
+/// angles it has no gimbal-lock coupling near the poles and composes cleanly
+/// for smooth, sub-degree camera moves.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Quaternion {
+    pub fn identity() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        }
+    }
+
+    /// The rotation of `radians` around `axis` (need not be unit length).
+    /// Returns the identity rotation if `axis` is (near) zero.
+    pub fn from_axis_angle(axis: Tuple3D, radians: f64) -> Self {
+        let length = axis.dot(&axis).sqrt();
+        if length < 1e-10 {
+            return Self::identity();
+        }
+
+        let unit = axis.scale(1.0 / length);
+        let half = radians / 2.0;
+        let (s, c) = (half.sin(), half.cos());
+
+        Self {
+            x: unit.x * s,
+            y: unit.y * s,
+            z: unit.z * s,
+            w: c,
+        }
+    }
+
+    /// Builds the rotation whose local x/y/z axes are `right`/`up`/`forward`
+    /// in world space, via the standard rotation-matrix-to-quaternion
+    /// conversion (the same one most look-at/build-camera routines use).
+    /// `right`, `up` and `forward` must be an orthonormal, right-handed basis.
+    pub fn from_basis(right: &Tuple3D, up: &Tuple3D, forward: &Tuple3D) -> Self {
+        let trace = right.x + up.y + forward.z;
+
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self {
+                w: 0.25 * s,
+                x: (up.z - forward.y) / s,
+                y: (forward.x - right.z) / s,
+                z: (right.y - up.x) / s,
+            }
+        } else if right.x > up.y && right.x > forward.z {
+            let s = (1.0 + right.x - up.y - forward.z).sqrt() * 2.0;
+            Self {
+                w: (up.z - forward.y) / s,
+                x: 0.25 * s,
+                y: (up.x + right.y) / s,
+                z: (forward.x + right.z) / s,
+            }
+        } else if up.y > forward.z {
+            let s = (1.0 + up.y - right.x - forward.z).sqrt() * 2.0;
+            Self {
+                w: (forward.x - right.z) / s,
+                x: (up.x + right.y) / s,
+                y: 0.25 * s,
+                z: (forward.y + up.z) / s,
+            }
+        } else {
+            let s = (1.0 + forward.z - right.x - up.y).sqrt() * 2.0;
+            Self {
+                w: (right.y - up.x) / s,
+                x: (forward.x + right.z) / s,
+                y: (forward.y + up.z) / s,
+                z: 0.25 * s,
+            }
+        }
+        .normalize()
+    }
+
+    pub fn normalize(&self) -> Self {
+        let length = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        Self {
+            x: self.x / length,
+            y: self.y / length,
+            z: self.z / length,
+            w: self.w / length,
+        }
+    }
+
+    /// Rotates `v` by this quaternion.
+    pub fn rotate(&self, v: &Tuple3D) -> Tuple3D {
+        let axis = Tuple3D {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        };
+        let t = axis.cross(v).scale(2.0);
+
+        v.add(&t.scale(self.w)).add(&axis.cross(&t))
+    }
+}
+
+impl std::ops::Mul for Quaternion {
+    type Output = Quaternion;
+
+    /// Hamilton product; `(self * other).rotate(v) == self.rotate(other.rotate(v))`.
+    fn mul(self, other: Quaternion) -> Quaternion {
+        Quaternion {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+}
+
 pub struct Plane {
     point1: Tuple3D,
     normal: Tuple3D,
@@ -90,10 +345,334 @@ impl Plane {
     }
 }
 
+/// A sphere used to test ray/segment intersection. No pipeline stage calls
+/// this yet - only its own tests do - so it's gated behind `#[cfg(test)]` to
+/// keep a plain `cargo build`/`clippy` free of dead-code warnings.
+#[cfg(test)]
+pub struct Sphere {
+    pub center: Tuple3D,
+    pub radius: f64,
+}
+
+#[cfg(test)]
+impl Sphere {
+    pub fn new(center: Tuple3D, radius: f64) -> Self {
+        Self { center, radius }
+    }
+
+    /// Returns the two intersection parameters `t` (where `point = start + t*(end-start)`)
+    /// of the segment `start -> end` with the sphere, or `None` if it misses.
+    pub fn intersect(&self, start: &Tuple3D, end: &Tuple3D) -> Option<(f64, f64)> {
+        let d = end.sub(start);
+        let oc = start.sub(&self.center);
+
+        let a = d.dot(&d);
+        let b = 2.0 * oc.dot(&d);
+        let c = oc.dot(&oc) - self.radius * self.radius;
+
+        if a.abs() < 1e-10 {
+            return None;
+        }
+
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let sqrt_disc = discriminant.sqrt();
+        let t1 = (-b - sqrt_disc) / (2.0 * a);
+        let t2 = (-b + sqrt_disc) / (2.0 * a);
+
+        Some((t1, t2))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sphere_intersect_two_hits() {
+        let sphere = Sphere::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            1.0,
+        );
+
+        let start = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -5.0,
+        };
+        let end = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 5.0,
+        };
+
+        let (t1, t2) = sphere.intersect(&start, &end).unwrap();
+        assert!((t1 - 0.4).abs() < 1e-10);
+        assert!((t2 - 0.6).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_sphere_intersect_tangent() {
+        // Unit sphere at the origin; the segment runs at y=1, exactly one
+        // radius away from the center, so it grazes the sphere at a single
+        // point (z=0) instead of passing through it.
+        let sphere = Sphere::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            1.0,
+        );
+
+        let start = Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: -5.0,
+        };
+        let end = Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: 5.0,
+        };
+
+        let (t1, t2) = sphere.intersect(&start, &end).unwrap();
+        assert!((t1 - t2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_sphere_intersect_miss() {
+        let sphere = Sphere::new(
+            Tuple3D {
+                x: 0.0,
+                y: 2.0,
+                z: 0.0,
+            },
+            1.0,
+        );
+
+        let start = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -5.0,
+        };
+        let end = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 5.0,
+        };
+
+        assert!(sphere.intersect(&start, &end).is_none());
+    }
+
+    #[test]
+    fn test_sphere_intersect_degenerate_zero_length_segment() {
+        let sphere = Sphere::new(
+            Tuple3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            1.0,
+        );
+
+        let point = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        };
+
+        assert!(sphere.intersect(&point, &point).is_none());
+    }
+
+    #[test]
+    fn test_matrix4_identity_is_noop() {
+        let p = Tuple3D {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+
+        let result = Matrix4::identity().transform_point(&p);
+        assert!((result.x - p.x).abs() < 1e-10);
+        assert!((result.y - p.y).abs() < 1e-10);
+        assert!((result.z - p.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_matrix4_translation() {
+        let p = Tuple3D {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+
+        let result = Matrix4::translation(5.0, -3.0, 2.0).transform_point(&p);
+        assert!((result.x - 6.0).abs() < 1e-10);
+        assert!((result.y - -1.0).abs() < 1e-10);
+        assert!((result.z - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_matrix4_scaling() {
+        let p = Tuple3D {
+            x: 2.0,
+            y: 3.0,
+            z: 4.0,
+        };
+
+        let result = Matrix4::scaling(2.0, 3.0, 4.0).transform_point(&p);
+        assert!((result.x - 4.0).abs() < 1e-10);
+        assert!((result.y - 9.0).abs() < 1e-10);
+        assert!((result.z - 16.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_matrix4_chained_transforms_apply_right_to_left() {
+        let p = Tuple3D {
+            x: 1.0,
+            y: 0.0,
+            z: 1.0,
+        };
+
+        let scaling = Matrix4::scaling(5.0, 5.0, 5.0);
+        let rotation = Matrix4::rotation_x(std::f64::consts::FRAC_PI_2);
+        let translation = Matrix4::translation(10.0, 5.0, 7.0);
+
+        let chained = translation * rotation * scaling;
+        let result = chained.transform_point(&p);
+
+        assert!((result.x - 15.0).abs() < 1e-10);
+        assert!((result.y - 0.0).abs() < 1e-10);
+        assert!((result.z - 7.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_identity_is_noop() {
+        let p = Tuple3D {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+
+        let result = Quaternion::identity().rotate(&p);
+        assert!((result.x - p.x).abs() < 1e-10);
+        assert!((result.y - p.y).abs() < 1e-10);
+        assert!((result.z - p.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_rotate_matches_matrix_rotation() {
+        let p = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+
+        let angle = std::f64::consts::FRAC_PI_2;
+        let q = Quaternion::from_axis_angle(
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            angle,
+        );
+
+        let by_quaternion = q.rotate(&p);
+        let by_matrix = Matrix4::rotation_y(angle).transform_point(&p);
+
+        assert!((by_quaternion.x - by_matrix.x).abs() < 1e-10);
+        assert!((by_quaternion.y - by_matrix.y).abs() < 1e-10);
+        assert!((by_quaternion.z - by_matrix.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_mul_composes_right_operand_first() {
+        let p = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+
+        let yaw = Quaternion::from_axis_angle(
+            Tuple3D {
+                x: 0.0,
+                y: 1.0,
+                z: 0.0,
+            },
+            std::f64::consts::FRAC_PI_2,
+        );
+        let pitch = Quaternion::from_axis_angle(
+            Tuple3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            std::f64::consts::FRAC_PI_2,
+        );
+
+        let composed = (yaw * pitch).rotate(&p);
+        let sequential = yaw.rotate(&pitch.rotate(&p));
+
+        assert!((composed.x - sequential.x).abs() < 1e-10);
+        assert!((composed.y - sequential.y).abs() < 1e-10);
+        assert!((composed.z - sequential.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_from_basis_round_trips_axes() {
+        let right = Tuple3D {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let up = Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: -1.0,
+        };
+        let forward = Tuple3D {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        };
+
+        let q = Quaternion::from_basis(&right, &up, &forward);
+
+        let rotated_forward = q.rotate(&Tuple3D {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        });
+        assert!((rotated_forward.x - forward.x).abs() < 1e-10);
+        assert!((rotated_forward.y - forward.y).abs() < 1e-10);
+        assert!((rotated_forward.z - forward.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_apply_transform_updates_all_pixels() {
+        let mut pixels = vec![Pixel3D {
+            coordinate: Tuple3D {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+            },
+            color: (0, 0, 0),
+        }];
+
+        apply_transform(&mut pixels, &Matrix4::translation(1.0, 0.0, 0.0));
+
+        assert!((pixels[0].coordinate.x - 2.0).abs() < 1e-10);
+    }
+
     #[test]
     fn test_plane_intersect_normal_case() {
         let plane = Plane::new(
@@ -0,0 +1,41 @@
+//! Vector (SVG) export of the projected walk, an alternative to the
+//! rasterized PPM/GIF output in [`crate::render`] and [`crate::animate`].
+//! Each segment keeps its own gradient color as a `<line>` stroke instead of
+//! being blended into a shared pixel buffer, so the result stays crisp at
+//! any zoom level for post-processing in Inkscape/Illustrator - the same
+//! scene-dump-to-SVG idea pathfinder uses.
+
+use crate::camera::Orbit;
+use crate::space::Pixel3D;
+use std::fmt::Write as _;
+use std::io;
+
+/// Projects `pixels` through `orbit`'s camera and writes the walk as an SVG
+/// document of one `<line>` per surviving segment to `path`.
+pub fn write_svg(pixels: &[Pixel3D], orbit: &Orbit, path: &str) -> io::Result<()> {
+    let width = orbit.screen_width();
+    let height = orbit.screen_height();
+    let points = orbit.projection().project_points(pixels);
+
+    let mut body = String::new();
+    for pair in points.windows(2) {
+        let (Some((from, _)), Some((to, color))) = (pair[0], pair[1]) else {
+            continue;
+        };
+
+        writeln!(
+            body,
+            r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="rgb({},{},{})" stroke-width="1" stroke-linecap="round" />"#,
+            from.0, from.1, to.0, to.1, color.0, color.1, color.2
+        )
+        .expect("writing to a String cannot fail");
+    }
+
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n\
+         {body}</svg>\n"
+    );
+
+    std::fs::write(path, document)
+}